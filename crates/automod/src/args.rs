@@ -1,6 +1,18 @@
-use clap::{Arg, ArgMatches, Command};
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
 
 pub(crate) const CONFIG: &str = "config";
+pub(crate) const NOTIFICATIONS: &str = "notifications";
+pub(crate) const LIST: &str = "list";
+pub(crate) const REPLAY: &str = "replay";
+pub(crate) const IDS: &str = "ids";
+pub(crate) const ALL: &str = "all";
+pub(crate) const MODLOG: &str = "modlog";
+pub(crate) const EXPORT: &str = "export";
+pub(crate) const SINCE: &str = "since";
+pub(crate) const TYPE: &str = "type";
+pub(crate) const INSTANCE: &str = "instance";
+pub(crate) const FORMAT: &str = "format";
 
 pub(crate) fn parse() -> ArgMatches {
     Command::new("AutoMod")
@@ -8,7 +20,74 @@ pub(crate) fn parse() -> ArgMatches {
             Arg::new(CONFIG)
                 .short('c')
                 .default_value("automod.toml")
+                .global(true)
                 .help("Path to configuration file"),
         )
+        .subcommand(
+            Command::new(NOTIFICATIONS)
+                .about("Inspect and replay failed notification deliveries")
+                .subcommand_required(true)
+                .subcommand(Command::new(LIST).about("List failed deliveries"))
+                .subcommand(
+                    Command::new(REPLAY)
+                        .about("Retry failed deliveries")
+                        .arg(
+                            Arg::new(IDS)
+                                .num_args(1..)
+                                .value_parser(value_parser!(u64))
+                                .required_unless_present(ALL)
+                                .help("Ids of the deliveries to retry"),
+                        )
+                        .arg(
+                            Arg::new(ALL)
+                                .long(ALL)
+                                .action(ArgAction::SetTrue)
+                                .conflicts_with(IDS)
+                                .help("Retry all failed deliveries"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new(MODLOG)
+                .about("Query the modlog archive")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new(EXPORT)
+                        .about("Export archived modlog entries")
+                        .arg(
+                            Arg::new(SINCE)
+                                .long(SINCE)
+                                .value_parser(parse_since)
+                                .help("Only export entries since a date or RFC 3339 timestamp"),
+                        )
+                        .arg(
+                            Arg::new(TYPE)
+                                .long(TYPE)
+                                .help("Only export entries of a type, e.g. site_ban"),
+                        )
+                        .arg(
+                            Arg::new(INSTANCE)
+                                .long(INSTANCE)
+                                .help("Only export entries by moderators of an instance"),
+                        )
+                        .arg(
+                            Arg::new(FORMAT)
+                                .long(FORMAT)
+                                .value_parser(["jsonl", "csv"])
+                                .default_value("jsonl")
+                                .help("Output format"),
+                        ),
+                ),
+        )
         .get_matches()
 }
+
+fn parse_since(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc());
+    }
+
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|err| err.to_string())
+}
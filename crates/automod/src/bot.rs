@@ -1,20 +1,24 @@
 use crate::config::Plugins;
 use lemmy_client::Client;
+use plugin_common::notifier::Notifier;
+use plugin_common::state::State;
 use plugin_mod_log::ModLog;
 use plugin_private_message::PrivateMessage;
 use std::time::Duration;
 use tracing::info;
 
 pub struct Bot {
+    notifier: Notifier,
     mod_log: ModLog,
     private_message: PrivateMessage,
 }
 
 impl Bot {
-    pub fn new(config: Plugins) -> Self {
+    pub fn new(config: Plugins, notifier: Notifier, state: State) -> Self {
         Bot {
-            mod_log: ModLog::new(config.mod_log),
-            private_message: PrivateMessage::new(config.private_message),
+            notifier,
+            mod_log: ModLog::new(config.mod_log, state.clone()),
+            private_message: PrivateMessage::new(config.private_message, state),
         }
     }
 
@@ -24,8 +28,11 @@ impl Bot {
         loop {
             // Invoke each plugin
             // TODO: Run in parallel
-            self.mod_log.run(&client).await;
-            self.private_message.run(&client).await;
+            self.mod_log.run(&client, &mut self.notifier).await;
+            self.private_message.run(&client, &mut self.notifier).await;
+
+            // Deliver any notifications raised by plugins
+            self.notifier.flush(&client).await;
 
             // Await next iteration
             tokio::time::sleep(Duration::from_secs(60)).await;
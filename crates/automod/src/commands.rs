@@ -0,0 +1,58 @@
+use crate::args;
+use chrono::{DateTime, Utc};
+use clap::ArgMatches;
+use plugin_common::notifier::Notifier;
+use plugin_mod_log::archive;
+use plugin_mod_log::archive::{Archive, Filter};
+use plugin_mod_log::config::{ArchiveFormat, ModLog};
+use std::io;
+
+pub(crate) fn notifications_list(notifier: &Notifier) {
+    let dead_letters = notifier.dead_letters();
+    if dead_letters.is_empty() {
+        println!("No failed deliveries.");
+        return;
+    }
+
+    for outbound in dead_letters {
+        println!(
+            "{}\t{}\t{}\tattempts={}\terror={}",
+            outbound.id,
+            outbound.created.to_rfc3339(),
+            outbound.target,
+            outbound.attempts,
+            outbound.last_error.clone().unwrap_or_default()
+        );
+    }
+}
+
+pub(crate) fn notifications_replay(notifier: &Notifier, matches: &ArgMatches) {
+    let ids = match matches.get_flag(args::ALL) {
+        true => vec![],
+        false => matches
+            .get_many::<u64>(args::IDS)
+            .map(|ids| ids.copied().collect())
+            .unwrap_or_default(),
+    };
+
+    let count = notifier.request_replay(&ids);
+    println!(
+        "Queued {} failed deliveries, they are retried on the next run of the bot.",
+        count
+    );
+}
+
+pub(crate) fn modlog_export(config: &ModLog, matches: &ArgMatches) -> Result<(), String> {
+    let filter = Filter {
+        since: matches.get_one::<DateTime<Utc>>(args::SINCE).copied(),
+        kind: matches.get_one::<String>(args::TYPE).cloned(),
+        instance: matches.get_one::<String>(args::INSTANCE).cloned(),
+    };
+    let format = match matches.get_one::<String>(args::FORMAT).map(String::as_str) {
+        Some("csv") => ArchiveFormat::Csv,
+        _ => ArchiveFormat::Jsonl,
+    };
+
+    let records = Archive::new(config).read(&filter)?;
+    archive::write(&records, format, io::stdout().lock())
+}
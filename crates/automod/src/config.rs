@@ -1,3 +1,4 @@
+use plugin_common::config::{Notifications, State, Templates};
 use plugin_mod_log::config::ModLog;
 use plugin_private_message::config::PrivateMessage;
 use serde::Deserialize;
@@ -8,6 +9,12 @@ use tracing::error;
 pub struct Config {
     pub lemmy: Lemmy,
     pub plugins: Plugins,
+    #[serde(default)]
+    pub notifications: Notifications,
+    #[serde(default)]
+    pub state: State,
+    #[serde(default)]
+    pub templates: Templates,
 }
 
 impl Config {
@@ -1,10 +1,14 @@
 mod args;
 mod bot;
+mod commands;
 mod config;
 
 use crate::bot::Bot;
 use config::Config;
 use lemmy_client::Client;
+use plugin_common::notifier::Notifier;
+use plugin_common::state::State;
+use plugin_common::template::Templates;
 use std::process::exit;
 use tracing::error;
 
@@ -29,6 +33,34 @@ async fn main() {
         Some(result) => result,
     };
 
+    // Load persisted state, message templates and notification routes
+    let state = State::new(&config.state);
+    let templates = Templates::load(&config.templates);
+    let notifier = Notifier::new(config.notifications, templates, state.clone());
+
+    // Inspect failed deliveries without requiring authentication
+    let notifications = args.subcommand_matches(args::NOTIFICATIONS);
+    if let Some((args::LIST, _)) = notifications.and_then(|matches| matches.subcommand()) {
+        commands::notifications_list(&notifier);
+        return;
+    }
+
+    // Request failed deliveries to be retried by the running bot
+    if let Some((args::REPLAY, matches)) = notifications.and_then(|matches| matches.subcommand()) {
+        commands::notifications_replay(&notifier, matches);
+        return;
+    }
+
+    // Export the modlog archive without requiring authentication
+    let modlog = args.subcommand_matches(args::MODLOG);
+    if let Some((args::EXPORT, matches)) = modlog.and_then(|matches| matches.subcommand()) {
+        if let Err(err) = commands::modlog_export(&config.plugins.mod_log, matches) {
+            error!("failed to export modlog: {}", err);
+            exit(1);
+        }
+        return;
+    }
+
     // Create API client with configured credentials
     let bot_user = config.lemmy;
     let client = match Client::new(bot_user.host, bot_user.username, bot_user.password).await {
@@ -40,6 +72,6 @@ async fn main() {
     };
 
     // Create and run bot
-    let mut bot = Bot::new(config.plugins);
+    let mut bot = Bot::new(config.plugins, notifier, state);
     bot.run(client).await;
 }
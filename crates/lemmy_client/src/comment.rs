@@ -1,6 +1,6 @@
-use crate::endpoints::COMMENT_REPORT;
+use crate::endpoints::{COMMENT_REMOVE, COMMENT_REPORT};
 use crate::{Client, ClientError};
-use lemmy_api_common::comment::CreateCommentReport;
+use lemmy_api_common::comment::{CreateCommentReport, RemoveComment};
 use lemmy_api_common::lemmy_db_schema::newtypes::CommentId;
 use reqwest::StatusCode;
 
@@ -27,3 +27,29 @@ pub async fn comment_report(
         status => Err(ClientError::new(path, status.to_string())),
     }
 }
+
+pub async fn comment_remove(
+    client: &Client,
+    comment_id: i32,
+    removed: bool,
+    reason: Option<String>,
+) -> Result<(), ClientError> {
+    // Create and perform request
+    let path = COMMENT_REMOVE;
+    let body = RemoveComment {
+        comment_id: CommentId(comment_id),
+        removed,
+        reason,
+    };
+    let result = client.post(path, true).json(&body).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
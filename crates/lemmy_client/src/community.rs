@@ -0,0 +1,108 @@
+use crate::endpoints::{COMMUNITY, COMMUNITY_BAN};
+use crate::model::{Community, Person};
+use crate::{Client, ClientError};
+use chrono::{DateTime, Utc};
+use lemmy_api_common::community::{BanFromCommunity, GetCommunity, GetCommunityResponse};
+use lemmy_api_common::lemmy_db_schema::newtypes::{CommunityId, PersonId};
+use reqwest::StatusCode;
+
+pub async fn community_get(client: &Client, name: String) -> Result<Community, ClientError> {
+    // Create and perform request
+    let path = COMMUNITY;
+    let params = GetCommunity {
+        name: Some(name),
+        ..Default::default()
+    };
+    let result = client.get(path, false).query(&params).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => {
+            // Parse response body
+            let body = response.json::<GetCommunityResponse>().await;
+            if let Err(err) = body {
+                return Err(ClientError::new(path, err.to_string()));
+            }
+            let community = body.ok().unwrap();
+
+            Ok(Community::from(community.community_view.community))
+        }
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
+
+pub async fn community_ban(
+    client: &Client,
+    community_id: i32,
+    person_id: i32,
+    ban: bool,
+    remove_content: Option<bool>,
+    reason: Option<String>,
+    expires: Option<DateTime<Utc>>,
+) -> Result<(), ClientError> {
+    // Create request
+    let path = COMMUNITY_BAN;
+    let params = BanFromCommunity {
+        community_id: CommunityId(community_id),
+        person_id: PersonId(person_id),
+        ban,
+        remove_data: remove_content,
+        reason,
+        expires: expires.map(|exp| exp.timestamp()),
+    };
+
+    // Perform request
+    let result = client.post(path, true).json(&params).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
+
+pub async fn community_moderators_get(
+    client: &Client,
+    name: String,
+) -> Result<Vec<Person>, ClientError> {
+    // Create and perform request
+    let path = COMMUNITY;
+    let params = GetCommunity {
+        name: Some(name),
+        ..Default::default()
+    };
+    let result = client.get(path, false).query(&params).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => {
+            // Parse response body
+            let body = response.json::<GetCommunityResponse>().await;
+            if let Err(err) = body {
+                return Err(ClientError::new(path, err.to_string()));
+            }
+            let community = body.ok().unwrap();
+
+            let moderators = community
+                .moderators
+                .iter()
+                .map(|view| Person::from(view.moderator.clone()))
+                .collect::<Vec<Person>>();
+
+            Ok(moderators)
+        }
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
@@ -1,12 +1,21 @@
 pub const ADMIN_PURGE_USER: &str = "/api/v3/admin/purge/person";
+pub const COMMENT_REMOVE: &str = "/api/v3/comment/remove";
 pub const COMMENT_REPORT: &str = "/api/v3/comment/report";
+pub const COMMUNITY: &str = "/api/v3/community";
+pub const COMMUNITY_BAN: &str = "/api/v3/community/ban_user";
 pub const LOGIN: &str = "/api/v3/user/login";
 pub const MODLOG: &str = "/api/v3/modlog";
+pub const POST_FEATURE: &str = "/api/v3/post/feature";
+pub const POST_LOCK: &str = "/api/v3/post/lock";
+pub const POST_REMOVE: &str = "/api/v3/post/remove";
 pub const POST_REPORT: &str = "/api/v3/post/report";
 pub const PRIVATE_MESSAGE: &str = "/api/v3/private_message";
 pub const PRIVATE_MESSAGE_DELETE: &str = "/api/v3/private_message/delete";
 pub const PRIVATE_MESSAGE_LIST: &str = "/api/v3/private_message/list";
 pub const PRIVATE_MESSAGE_READ: &str = "/api/v3/private_message/mark_as_read";
+pub const PRIVATE_MESSAGE_REPORT: &str = "/api/v3/private_message/report";
+pub const RESOLVE_OBJECT: &str = "/api/v3/resolve_object";
 pub const SITE: &str = "/api/v3/site";
 pub const USER: &str = "/api/v3/user";
 pub const USER_BAN: &str = "/api/v3/user/ban";
+pub const USER_BLOCK: &str = "/api/v3/user/block";
@@ -6,6 +6,7 @@ use std::fmt::{Display, Formatter};
 
 pub mod auth;
 pub mod comment;
+pub mod community;
 pub mod endpoints;
 pub mod model;
 pub mod modlog;
@@ -1,8 +1,10 @@
 use chrono::{DateTime, Utc};
-use lemmy_api_common::lemmy_db_schema::newtypes::PrivateMessageId;
+pub use lemmy_api_common::lemmy_db_schema::newtypes::PrivateMessageId;
 use lemmy_api_common::lemmy_db_schema::source::person;
 use lemmy_api_common::lemmy_db_schema::source::private_message;
 use lemmy_api_common::lemmy_db_schema::source::{comment, community, post};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use url::Url;
 
@@ -26,8 +28,16 @@ impl Authentication {
 
 #[derive(Default)]
 pub struct ModlogActions {
+    pub cursor: ModlogCursor,
     pub bans: Vec<ModlogBan>,
     pub removals: Vec<ModlogRemoval>,
+    pub community_removals: Vec<ModlogCommunityRemoval>,
+    pub locks: Vec<ModlogLock>,
+    pub features: Vec<ModlogFeature>,
+    pub mod_adds: Vec<ModlogModAdd>,
+    pub transfers: Vec<ModlogTransfer>,
+    pub hides: Vec<ModlogHide>,
+    pub purges: Vec<ModlogPurge>,
 }
 
 impl ModlogActions {
@@ -36,6 +46,137 @@ impl ModlogActions {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ModlogKind {
+    SiteBan,
+    CommunityBan,
+    CommentRemoval,
+    PostRemoval,
+    CommunityRemoval,
+    PostLock,
+    PostFeature,
+    AdminAdd,
+    ModAdd,
+    CommunityTransfer,
+    CommunityHide,
+    PersonPurge,
+    CommunityPurge,
+    PostPurge,
+    CommentPurge,
+}
+
+impl Display for ModlogKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ModlogKind::SiteBan => "site_ban",
+            ModlogKind::CommunityBan => "community_ban",
+            ModlogKind::CommentRemoval => "comment_removal",
+            ModlogKind::PostRemoval => "post_removal",
+            ModlogKind::CommunityRemoval => "community_removal",
+            ModlogKind::PostLock => "post_lock",
+            ModlogKind::PostFeature => "post_feature",
+            ModlogKind::AdminAdd => "admin_add",
+            ModlogKind::ModAdd => "mod_add",
+            ModlogKind::CommunityTransfer => "community_transfer",
+            ModlogKind::CommunityHide => "community_hide",
+            ModlogKind::PersonPurge => "person_purge",
+            ModlogKind::CommunityPurge => "community_purge",
+            ModlogKind::PostPurge => "post_purge",
+            ModlogKind::CommentPurge => "comment_purge",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Highest processed id of each modlog table.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ModlogCursor {
+    ids: HashMap<ModlogKind, i32>,
+}
+
+impl ModlogCursor {
+    pub fn new() -> Self {
+        ModlogCursor::default()
+    }
+
+    /// Whether an entry is past the cursor, tables without an id yet start from the beginning.
+    pub fn is_new(&self, kind: ModlogKind, id: i32) -> bool {
+        id > self.ids.get(&kind).copied().unwrap_or_default()
+    }
+
+    pub fn advance(&mut self, kind: ModlogKind, id: i32) {
+        let current = self.ids.entry(kind).or_default();
+        *current = id.max(*current);
+    }
+
+    /// Whether no table has been processed yet, e.g. on the first run.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// A single, unfiltered modlog entry of any kind.
+pub struct ModlogEntry {
+    pub id: i32,
+    pub kind: ModlogKind,
+    pub when: DateTime<Utc>,
+    pub moderator: Option<Person>,
+    pub user: Option<Person>,
+    pub community: Option<Community>,
+    pub target: Option<String>,
+    pub reason: Option<String>,
+    pub is_active: bool,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl ModlogEntry {
+    pub fn new(id: i32, kind: ModlogKind, when: DateTime<Utc>, moderator: Option<Person>) -> Self {
+        ModlogEntry {
+            id,
+            kind,
+            when,
+            moderator,
+            user: None,
+            community: None,
+            target: None,
+            reason: None,
+            is_active: true,
+            expires: None,
+        }
+    }
+}
+
+impl Display for ModlogEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let describe = |value: Option<String>| value.unwrap_or("N/A".to_string());
+        write!(
+            f,
+            "* {} = `{}`\r\n\
+            * user = {}\r\n\
+            * community = {}\r\n\
+            * target = {}\r\n\
+            * mod = {}\r\n\
+            * reason = `{}`",
+            self.kind,
+            self.is_active,
+            describe(self.user.as_ref().map(|user| user.to_string())),
+            describe(
+                self.community
+                    .as_ref()
+                    .map(|community| community.to_string())
+            ),
+            describe(self.target.clone()),
+            describe(
+                self.moderator
+                    .as_ref()
+                    .map(|moderator| moderator.to_string())
+            ),
+            self.reason.clone().unwrap_or_default()
+        )
+    }
+}
+
 pub enum ModlogBan {
     Site {
         moderator: Person,
@@ -54,6 +195,29 @@ pub enum ModlogBan {
     },
 }
 
+impl ModlogBan {
+    pub fn moderator(&self) -> &Person {
+        match self {
+            ModlogBan::Site { moderator, .. } => moderator,
+            ModlogBan::Community { moderator, .. } => moderator,
+        }
+    }
+
+    pub fn user(&self) -> &Person {
+        match self {
+            ModlogBan::Site { user, .. } => user,
+            ModlogBan::Community { user, .. } => user,
+        }
+    }
+
+    pub fn is_banned(&self) -> bool {
+        match self {
+            ModlogBan::Site { is_banned, .. } => *is_banned,
+            ModlogBan::Community { is_banned, .. } => *is_banned,
+        }
+    }
+}
+
 impl Display for ModlogBan {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -119,6 +283,7 @@ pub struct ModlogCommentRemoval {
     pub moderator: Person,
     pub user: Person,
     pub comment: Comment,
+    pub community: Community,
     pub is_removed: bool,
     pub reason: Option<String>,
 }
@@ -145,6 +310,7 @@ pub struct ModlogPostRemoval {
     pub moderator: Person,
     pub user: Person,
     pub post: Post,
+    pub community: Community,
     pub is_removed: bool,
     pub reason: Option<String>,
 }
@@ -167,6 +333,261 @@ impl Display for ModlogPostRemoval {
     }
 }
 
+pub struct ModlogCommunityRemoval {
+    pub moderator: Person,
+    pub community: Community,
+    pub is_removed: bool,
+    pub reason: Option<String>,
+}
+
+impl Display for ModlogCommunityRemoval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "* community_removed = `{}`\r\n\
+            * community = {}\r\n\
+            * mod = {}\r\n\
+            * reason = `{}`",
+            self.is_removed,
+            self.community,
+            self.moderator,
+            self.reason.clone().unwrap_or_default()
+        )
+    }
+}
+
+pub struct ModlogLock {
+    pub moderator: Person,
+    pub post: Post,
+    pub community: Community,
+    pub is_locked: bool,
+}
+
+impl Display for ModlogLock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "* post_locked = `{}`\r\n\
+            * post = {}\r\n\
+            * community = {}\r\n\
+            * mod = {}",
+            self.is_locked, self.post, self.community, self.moderator
+        )
+    }
+}
+
+pub struct ModlogFeature {
+    pub moderator: Person,
+    pub post: Post,
+    pub community: Community,
+    pub is_featured: bool,
+    pub is_community: bool,
+}
+
+impl Display for ModlogFeature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "* post_featured = `{}`\r\n\
+            * scope = `{}`\r\n\
+            * post = {}\r\n\
+            * community = {}\r\n\
+            * mod = {}",
+            self.is_featured,
+            if self.is_community {
+                "community"
+            } else {
+                "site"
+            },
+            self.post,
+            self.community,
+            self.moderator
+        )
+    }
+}
+
+pub enum ModlogModAdd {
+    Site {
+        moderator: Person,
+        user: Person,
+        is_added: bool,
+    },
+    Community {
+        moderator: Person,
+        user: Person,
+        community: Community,
+        is_added: bool,
+    },
+}
+
+impl Display for ModlogModAdd {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModlogModAdd::Site {
+                moderator,
+                user,
+                is_added,
+            } => {
+                write!(
+                    f,
+                    "* admin_added = `{}`\r\n\
+                    * user = {}\r\n\
+                    * mod = {}",
+                    is_added, user, moderator
+                )
+            }
+            ModlogModAdd::Community {
+                moderator,
+                user,
+                community,
+                is_added,
+            } => {
+                write!(
+                    f,
+                    "* mod_added = `{}`\r\n\
+                    * user = {}\r\n\
+                    * community = {}\r\n\
+                    * mod = {}",
+                    is_added, user, community, moderator
+                )
+            }
+        }
+    }
+}
+
+pub struct ModlogTransfer {
+    pub moderator: Person,
+    pub user: Person,
+    pub community: Community,
+}
+
+impl Display for ModlogTransfer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "* community_transferred = `true`\r\n\
+            * user = {}\r\n\
+            * community = {}\r\n\
+            * mod = {}",
+            self.user, self.community, self.moderator
+        )
+    }
+}
+
+pub struct ModlogHide {
+    pub moderator: Person,
+    pub community: Community,
+    pub is_hidden: bool,
+    pub reason: Option<String>,
+}
+
+impl Display for ModlogHide {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "* community_hidden = `{}`\r\n\
+            * community = {}\r\n\
+            * mod = {}\r\n\
+            * reason = `{}`",
+            self.is_hidden,
+            self.community,
+            self.moderator,
+            self.reason.clone().unwrap_or_default()
+        )
+    }
+}
+
+pub enum ModlogPurge {
+    Person {
+        admin: Person,
+        reason: Option<String>,
+    },
+    Community {
+        admin: Person,
+        reason: Option<String>,
+    },
+    Post {
+        admin: Person,
+        community: Community,
+        reason: Option<String>,
+    },
+    Comment {
+        admin: Person,
+        post: Post,
+        reason: Option<String>,
+    },
+}
+
+impl ModlogPurge {
+    pub fn admin(&self) -> &Person {
+        match self {
+            ModlogPurge::Person { admin, .. } => admin,
+            ModlogPurge::Community { admin, .. } => admin,
+            ModlogPurge::Post { admin, .. } => admin,
+            ModlogPurge::Comment { admin, .. } => admin,
+        }
+    }
+}
+
+impl Display for ModlogPurge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModlogPurge::Person { admin, reason } => {
+                write!(
+                    f,
+                    "* purged = `person`\r\n\
+                    * admin = {}\r\n\
+                    * reason = `{}`",
+                    admin,
+                    reason.clone().unwrap_or_default()
+                )
+            }
+            ModlogPurge::Community { admin, reason } => {
+                write!(
+                    f,
+                    "* purged = `community`\r\n\
+                    * admin = {}\r\n\
+                    * reason = `{}`",
+                    admin,
+                    reason.clone().unwrap_or_default()
+                )
+            }
+            ModlogPurge::Post {
+                admin,
+                community,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "* purged = `post`\r\n\
+                    * community = {}\r\n\
+                    * admin = {}\r\n\
+                    * reason = `{}`",
+                    community,
+                    admin,
+                    reason.clone().unwrap_or_default()
+                )
+            }
+            ModlogPurge::Comment {
+                admin,
+                post,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "* purged = `comment`\r\n\
+                    * post = {}\r\n\
+                    * admin = {}\r\n\
+                    * reason = `{}`",
+                    post,
+                    admin,
+                    reason.clone().unwrap_or_default()
+                )
+            }
+        }
+    }
+}
+
 pub struct Comment {
     pub id: i32,
     pub content: String,
@@ -214,18 +635,22 @@ impl Display for Post {
 }
 
 pub struct Community {
+    pub id: i32,
     pub name: String,
     pub instance: String,
     pub url: String,
+    pub is_local: bool,
 }
 
 impl From<community::Community> for Community {
     fn from(value: community::Community) -> Self {
         let url: Url = value.actor_id.into();
         Community {
+            id: value.id.0,
             name: value.name,
             instance: url.host().unwrap().to_string(),
             url: url.to_string(),
+            is_local: value.local,
         }
     }
 }
@@ -242,6 +667,7 @@ pub struct Person {
     pub instance: String,
     pub url: String,
     pub is_local: bool,
+    pub created: DateTime<Utc>,
 }
 
 impl From<person::Person> for Person {
@@ -253,6 +679,7 @@ impl From<person::Person> for Person {
             instance: url.host().unwrap().to_string(),
             url: url.to_string(),
             is_local: value.local,
+            created: value.published,
         }
     }
 }
@@ -263,9 +690,39 @@ impl Display for Person {
     }
 }
 
+/// Object resolved from a link, along with the community it belongs to.
+pub enum Object {
+    Comment(Comment, Community),
+    Post(Post, Community),
+    Community(Community),
+    Person(Person),
+}
+
+impl Object {
+    pub fn community(&self) -> Option<&Community> {
+        match self {
+            Object::Comment(_, community) | Object::Post(_, community) => Some(community),
+            Object::Community(community) => Some(community),
+            Object::Person(_) => None,
+        }
+    }
+}
+
+impl Display for Object {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Object::Comment(comment, _) => write!(f, "{}", comment),
+            Object::Post(post, _) => write!(f, "{}", post),
+            Object::Community(community) => write!(f, "{}", community),
+            Object::Person(person) => write!(f, "{}", person),
+        }
+    }
+}
+
 pub struct PrivateMessage {
     pub id: PrivateMessageId, // NOTE: Inner value is private
     pub sender_id: i32,
+    pub recipient_id: i32,
     pub content: String,
     pub created: DateTime<Utc>,
 }
@@ -275,6 +732,7 @@ impl From<private_message::PrivateMessage> for PrivateMessage {
         PrivateMessage {
             id: value.id,
             sender_id: value.creator_id.0,
+            recipient_id: value.recipient_id.0,
             content: value.content,
             created: value.published,
         }
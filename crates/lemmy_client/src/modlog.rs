@@ -1,26 +1,526 @@
 use crate::endpoints::MODLOG;
 use crate::model::{
-    Comment, Community, ModlogActions, ModlogBan, ModlogCommentRemoval, ModlogPostRemoval,
-    ModlogRemoval, Person, Post,
+    Comment, Community, ModlogActions, ModlogBan, ModlogCommentRemoval, ModlogCommunityRemoval,
+    ModlogCursor, ModlogEntry, ModlogFeature, ModlogHide, ModlogKind, ModlogLock, ModlogModAdd,
+    ModlogPostRemoval, ModlogPurge, ModlogRemoval, ModlogTransfer, Person, Post,
 };
 use crate::person::{person_get, PersonRef};
 use crate::{Client, ClientError};
 use chrono::{DateTime, Utc};
+use lemmy_api_common::lemmy_db_schema::newtypes::{CommunityId, PersonId};
+use lemmy_api_common::lemmy_db_schema::source::person;
+use lemmy_api_common::lemmy_db_schema::ModlogActionType;
 use lemmy_api_common::lemmy_db_views_moderator::structs::{
-    ModBanFromCommunityView, ModBanView, ModRemoveCommentView, ModRemovePostView,
+    AdminPurgeCommentView, AdminPurgeCommunityView, AdminPurgePersonView, AdminPurgePostView,
+    ModAddCommunityView, ModAddView, ModBanFromCommunityView, ModBanView, ModFeaturePostView,
+    ModHideCommunityView, ModLockPostView, ModRemoveCommentView, ModRemoveCommunityView,
+    ModRemovePostView, ModTransferCommunityView,
 };
 use lemmy_api_common::site::{GetModlog, GetModlogResponse};
 use reqwest::StatusCode;
+use url::Url;
 
+const DEFAULT_LIMIT: i64 = 50;
+// Upper bound on pages fetched to catch up with a cursor, e.g. after a long outage
+const MAX_PAGES: i64 = 20;
+
+/// Filters for modlog requests. Apart from `since`, these are applied by the server.
+#[derive(Clone)]
+pub struct ModlogQuery {
+    kind: Option<ModlogKind>,
+    community_id: Option<i32>,
+    moderator_id: Option<i32>,
+    user_id: Option<i32>,
+    since: Option<DateTime<Utc>>,
+    limit: i64,
+    page: i64,
+}
+
+impl ModlogQuery {
+    pub fn new() -> Self {
+        ModlogQuery::default()
+    }
+
+    /// Only include entries of a single kind, e.g. site bans.
+    pub fn kind(mut self, kind: ModlogKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Only include entries within a community.
+    pub fn community(mut self, community_id: i32) -> Self {
+        self.community_id = Some(community_id);
+        self
+    }
+
+    /// Only include entries performed by a moderator.
+    pub fn moderator(mut self, person_id: i32) -> Self {
+        self.moderator_id = Some(person_id);
+        self
+    }
+
+    /// Only include entries performed against a user.
+    pub fn user(mut self, person_id: i32) -> Self {
+        self.user_id = Some(person_id);
+        self
+    }
+
+    /// Only include entries since the given time.
+    pub fn since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Maximum number of entries per modlog table.
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Page of entries per modlog table, starting at 1.
+    pub fn page(mut self, page: i64) -> Self {
+        self.page = page;
+        self
+    }
+}
+
+impl Default for ModlogQuery {
+    fn default() -> Self {
+        ModlogQuery {
+            kind: None,
+            community_id: None,
+            moderator_id: None,
+            user_id: None,
+            since: None,
+            limit: DEFAULT_LIMIT,
+            page: 1,
+        }
+    }
+}
+
+impl From<ModlogKind> for ModlogActionType {
+    fn from(value: ModlogKind) -> Self {
+        match value {
+            ModlogKind::SiteBan => ModlogActionType::ModBan,
+            ModlogKind::CommunityBan => ModlogActionType::ModBanFromCommunity,
+            ModlogKind::CommentRemoval => ModlogActionType::ModRemoveComment,
+            ModlogKind::PostRemoval => ModlogActionType::ModRemovePost,
+            ModlogKind::CommunityRemoval => ModlogActionType::ModRemoveCommunity,
+            ModlogKind::PostLock => ModlogActionType::ModLockPost,
+            ModlogKind::PostFeature => ModlogActionType::ModFeaturePost,
+            ModlogKind::AdminAdd => ModlogActionType::ModAdd,
+            ModlogKind::ModAdd => ModlogActionType::ModAddCommunity,
+            ModlogKind::CommunityTransfer => ModlogActionType::ModTransferCommunity,
+            ModlogKind::CommunityHide => ModlogActionType::ModHideCommunity,
+            ModlogKind::PersonPurge => ModlogActionType::AdminPurgePerson,
+            ModlogKind::CommunityPurge => ModlogActionType::AdminPurgeCommunity,
+            ModlogKind::PostPurge => ModlogActionType::AdminPurgePost,
+            ModlogKind::CommentPurge => ModlogActionType::AdminPurgeComment,
+        }
+    }
+}
+
+/// Gets the actions of remote moderators against local users past the cursor, along with the
+/// cursor moved past every entry seen.
 pub async fn modlog_local_get(
     client: &Client,
-    since: DateTime<Utc>,
+    cursor: &ModlogCursor,
+) -> Result<ModlogActions, ClientError> {
+    let modlog = modlog_cursor_get(client, cursor).await?;
+    let origin = Origin::Remote;
+
+    let mut actions = ModlogActions::new();
+    actions.cursor = modlog_cursor(cursor, &modlog);
+
+    // Filter Site Bans
+    if let Err(err) = get_site_bans(client, modlog.banned, cursor, origin, &mut actions.bans).await
+    {
+        return Err(ClientError::new(MODLOG, err.to_string()));
+    }
+
+    // Filter Community Bans
+    if let Err(err) = get_community_bans(
+        client,
+        modlog.banned_from_community,
+        cursor,
+        origin,
+        &mut actions.bans,
+    )
+    .await
+    {
+        return Err(ClientError::new(MODLOG, err.to_string()));
+    }
+
+    // Filter Comment Removals
+    if let Err(err) = get_comment_removals(
+        client,
+        modlog.removed_comments,
+        cursor,
+        origin,
+        &mut actions.removals,
+    )
+    .await
+    {
+        return Err(ClientError::new(MODLOG, err.to_string()));
+    }
+
+    // Filter Post Removals
+    if let Err(err) = get_post_removals(
+        client,
+        modlog.removed_posts,
+        cursor,
+        origin,
+        &mut actions.removals,
+    )
+    .await
+    {
+        return Err(ClientError::new(MODLOG, err.to_string()));
+    }
+
+    // Filter Community Removals
+    get_community_removals(
+        modlog.removed_communities,
+        cursor,
+        &mut actions.community_removals,
+    );
+
+    // Filter Post Locks
+    get_post_locks(modlog.locked_posts, cursor, &mut actions.locks);
+
+    // Filter Post Features
+    get_post_features(modlog.featured_posts, cursor, &mut actions.features);
+
+    // Filter Admin Additions
+    get_admin_adds(modlog.added, cursor, &mut actions.mod_adds);
+
+    // Filter Moderator Additions
+    get_mod_adds(modlog.added_to_community, cursor, &mut actions.mod_adds);
+
+    // Filter Community Transfers
+    get_community_transfers(
+        modlog.transferred_to_community,
+        cursor,
+        &mut actions.transfers,
+    );
+
+    // Filter Community Hides
+    get_community_hides(modlog.hidden_communities, cursor, &mut actions.hides);
+
+    // Filter Admin Purges
+    get_purges(
+        Purges {
+            persons: modlog.admin_purged_persons,
+            communities: modlog.admin_purged_communities,
+            posts: modlog.admin_purged_posts,
+            comments: modlog.admin_purged_comments,
+        },
+        cursor,
+        &mut actions.purges,
+    );
+
+    Ok(actions)
+}
+
+/// Gets the bans and removals performed by local moderators against any user past the cursor,
+/// along with the cursor moved past every entry seen.
+pub async fn modlog_moderators_get(
+    client: &Client,
+    cursor: &ModlogCursor,
 ) -> Result<ModlogActions, ClientError> {
+    let modlog = modlog_cursor_get(client, cursor).await?;
+    let origin = Origin::Local;
+
+    let mut actions = ModlogActions::new();
+    actions.cursor = modlog_cursor(cursor, &modlog);
+
+    // Filter Site Bans
+    if let Err(err) = get_site_bans(client, modlog.banned, cursor, origin, &mut actions.bans).await
+    {
+        return Err(ClientError::new(MODLOG, err.to_string()));
+    }
+
+    // Filter Community Bans
+    if let Err(err) = get_community_bans(
+        client,
+        modlog.banned_from_community,
+        cursor,
+        origin,
+        &mut actions.bans,
+    )
+    .await
+    {
+        return Err(ClientError::new(MODLOG, err.to_string()));
+    }
+
+    // Filter Comment Removals
+    if let Err(err) = get_comment_removals(
+        client,
+        modlog.removed_comments,
+        cursor,
+        origin,
+        &mut actions.removals,
+    )
+    .await
+    {
+        return Err(ClientError::new(MODLOG, err.to_string()));
+    }
+
+    // Filter Post Removals
+    if let Err(err) = get_post_removals(
+        client,
+        modlog.removed_posts,
+        cursor,
+        origin,
+        &mut actions.removals,
+    )
+    .await
+    {
+        return Err(ClientError::new(MODLOG, err.to_string()));
+    }
+
+    Ok(actions)
+}
+
+/// Gets the most recent entries matching the query, oldest first.
+pub async fn modlog_entries_get(
+    client: &Client,
+    query: &ModlogQuery,
+) -> Result<Vec<ModlogEntry>, ClientError> {
+    let modlog = modlog_get(client, query).await?;
+    let moderator = |person: Option<person::Person>| person.map(Person::from);
+    let url = |value: Url| Some(value.to_string());
+
+    let mut entries = vec![];
+    for view in modlog.banned {
+        let action = view.mod_ban;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::SiteBan,
+            action.when_,
+            moderator(view.moderator),
+        );
+        entry.user = Some(Person::from(view.banned_person));
+        entry.reason = action.reason;
+        entry.is_active = action.banned;
+        entry.expires = action.expires;
+        entries.push(entry);
+    }
+
+    for view in modlog.banned_from_community {
+        let action = view.mod_ban_from_community;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::CommunityBan,
+            action.when_,
+            moderator(view.moderator),
+        );
+        entry.user = Some(Person::from(view.banned_person));
+        entry.community = Some(Community::from(view.community));
+        entry.reason = action.reason;
+        entry.is_active = action.banned;
+        entry.expires = action.expires;
+        entries.push(entry);
+    }
+
+    for view in modlog.removed_comments {
+        let action = view.mod_remove_comment;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::CommentRemoval,
+            action.when_,
+            moderator(view.moderator),
+        );
+        entry.user = Some(Person::from(view.commenter));
+        entry.community = Some(Community::from(view.community));
+        entry.target = url(view.comment.ap_id.into());
+        entry.reason = action.reason;
+        entry.is_active = action.removed;
+        entries.push(entry);
+    }
+
+    for view in modlog.removed_posts {
+        let action = view.mod_remove_post;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::PostRemoval,
+            action.when_,
+            moderator(view.moderator),
+        );
+        entry.community = Some(Community::from(view.community));
+        entry.target = url(view.post.ap_id.into());
+        entry.reason = action.reason;
+        entry.is_active = action.removed;
+        entries.push(entry);
+    }
+
+    for view in modlog.removed_communities {
+        let action = view.mod_remove_community;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::CommunityRemoval,
+            action.when_,
+            moderator(view.moderator),
+        );
+        entry.community = Some(Community::from(view.community));
+        entry.reason = action.reason;
+        entry.is_active = action.removed;
+        entries.push(entry);
+    }
+
+    for view in modlog.locked_posts {
+        let action = view.mod_lock_post;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::PostLock,
+            action.when_,
+            moderator(view.moderator),
+        );
+        entry.community = Some(Community::from(view.community));
+        entry.target = url(view.post.ap_id.into());
+        entry.is_active = action.locked;
+        entries.push(entry);
+    }
+
+    for view in modlog.featured_posts {
+        let action = view.mod_feature_post;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::PostFeature,
+            action.when_,
+            moderator(view.moderator),
+        );
+        entry.community = Some(Community::from(view.community));
+        entry.target = url(view.post.ap_id.into());
+        entry.is_active = action.featured;
+        entries.push(entry);
+    }
+
+    for view in modlog.added {
+        let action = view.mod_add;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::AdminAdd,
+            action.when_,
+            moderator(view.moderator),
+        );
+        entry.user = Some(Person::from(view.modded_person));
+        entry.is_active = !action.removed;
+        entries.push(entry);
+    }
+
+    for view in modlog.added_to_community {
+        let action = view.mod_add_community;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::ModAdd,
+            action.when_,
+            moderator(view.moderator),
+        );
+        entry.user = Some(Person::from(view.modded_person));
+        entry.community = Some(Community::from(view.community));
+        entry.is_active = !action.removed;
+        entries.push(entry);
+    }
+
+    for view in modlog.transferred_to_community {
+        let action = view.mod_transfer_community;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::CommunityTransfer,
+            action.when_,
+            moderator(view.moderator),
+        );
+        entry.user = Some(Person::from(view.modded_person));
+        entry.community = Some(Community::from(view.community));
+        entries.push(entry);
+    }
+
+    for view in modlog.hidden_communities {
+        let action = view.mod_hide_community;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::CommunityHide,
+            action.when_,
+            moderator(view.admin),
+        );
+        entry.community = Some(Community::from(view.community));
+        entry.reason = action.reason;
+        entry.is_active = action.hidden;
+        entries.push(entry);
+    }
+
+    for view in modlog.admin_purged_persons {
+        let action = view.admin_purge_person;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::PersonPurge,
+            action.when_,
+            moderator(view.admin),
+        );
+        entry.reason = action.reason;
+        entries.push(entry);
+    }
+
+    for view in modlog.admin_purged_communities {
+        let action = view.admin_purge_community;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::CommunityPurge,
+            action.when_,
+            moderator(view.admin),
+        );
+        entry.reason = action.reason;
+        entries.push(entry);
+    }
+
+    for view in modlog.admin_purged_posts {
+        let action = view.admin_purge_post;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::PostPurge,
+            action.when_,
+            moderator(view.admin),
+        );
+        entry.community = Some(Community::from(view.community));
+        entry.reason = action.reason;
+        entries.push(entry);
+    }
+
+    for view in modlog.admin_purged_comments {
+        let action = view.admin_purge_comment;
+        let mut entry = ModlogEntry::new(
+            action.id,
+            ModlogKind::CommentPurge,
+            action.when_,
+            moderator(view.admin),
+        );
+        entry.target = url(view.post.ap_id.into());
+        entry.reason = action.reason;
+        entries.push(entry);
+    }
+
+    // Filter entries that are older than the given timespan
+    if let Some(since) = query.since {
+        entries.retain(|entry| since <= entry.when);
+    }
+    entries.sort_by_key(|entry| entry.when);
+
+    Ok(entries)
+}
+
+async fn modlog_get(
+    client: &Client,
+    query: &ModlogQuery,
+) -> Result<GetModlogResponse, ClientError> {
     // Create and perform request
     let path = MODLOG;
     let body = GetModlog {
-        limit: Some(50),
-        ..Default::default()
+        type_: query.kind.map(ModlogActionType::from),
+        community_id: query.community_id.map(CommunityId),
+        mod_person_id: query.moderator_id.map(PersonId),
+        other_person_id: query.user_id.map(PersonId),
+        limit: Some(query.limit),
+        page: Some(query.page),
     };
     let result = client.get(path, true).query(&body).send().await;
     if let Err(err) = result {
@@ -36,65 +536,228 @@ pub async fn modlog_local_get(
             if let Err(err) = body {
                 return Err(ClientError::new(path, err.to_string()));
             }
-            let modlog = body.ok().unwrap();
-
-            let mut actions = ModlogActions::new();
+            Ok(body.ok().unwrap())
+        }
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
 
-            // Filter Site Bans
-            if let Err(err) = get_site_bans(client, modlog.banned, since, &mut actions.bans).await {
-                return Err(ClientError::new(path, err.to_string()));
-            }
+/// Ids of the entries of a response, per modlog table.
+fn modlog_ids(modlog: &GetModlogResponse) -> Vec<(ModlogKind, Vec<i32>)> {
+    let tables = [
+        (
+            ModlogKind::SiteBan,
+            modlog.banned.iter().map(|view| view.mod_ban.id).collect(),
+        ),
+        (
+            ModlogKind::CommunityBan,
+            modlog
+                .banned_from_community
+                .iter()
+                .map(|view| view.mod_ban_from_community.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::CommentRemoval,
+            modlog
+                .removed_comments
+                .iter()
+                .map(|view| view.mod_remove_comment.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::PostRemoval,
+            modlog
+                .removed_posts
+                .iter()
+                .map(|view| view.mod_remove_post.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::CommunityRemoval,
+            modlog
+                .removed_communities
+                .iter()
+                .map(|view| view.mod_remove_community.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::PostLock,
+            modlog
+                .locked_posts
+                .iter()
+                .map(|view| view.mod_lock_post.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::PostFeature,
+            modlog
+                .featured_posts
+                .iter()
+                .map(|view| view.mod_feature_post.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::AdminAdd,
+            modlog.added.iter().map(|view| view.mod_add.id).collect(),
+        ),
+        (
+            ModlogKind::ModAdd,
+            modlog
+                .added_to_community
+                .iter()
+                .map(|view| view.mod_add_community.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::CommunityTransfer,
+            modlog
+                .transferred_to_community
+                .iter()
+                .map(|view| view.mod_transfer_community.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::CommunityHide,
+            modlog
+                .hidden_communities
+                .iter()
+                .map(|view| view.mod_hide_community.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::PersonPurge,
+            modlog
+                .admin_purged_persons
+                .iter()
+                .map(|view| view.admin_purge_person.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::CommunityPurge,
+            modlog
+                .admin_purged_communities
+                .iter()
+                .map(|view| view.admin_purge_community.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::PostPurge,
+            modlog
+                .admin_purged_posts
+                .iter()
+                .map(|view| view.admin_purge_post.id)
+                .collect(),
+        ),
+        (
+            ModlogKind::CommentPurge,
+            modlog
+                .admin_purged_comments
+                .iter()
+                .map(|view| view.admin_purge_comment.id)
+                .collect(),
+        ),
+    ];
 
-            // Filter Community Bans
-            if let Err(err) = get_community_bans(
-                client,
-                modlog.banned_from_community,
-                since,
-                &mut actions.bans,
-            )
-            .await
-            {
-                return Err(ClientError::new(path, err.to_string()));
-            }
+    tables.into()
+}
 
-            // Filter Comment Removals
-            if let Err(err) = get_comment_removals(
-                client,
-                modlog.removed_comments,
-                since,
-                &mut actions.removals,
-            )
-            .await
-            {
-                return Err(ClientError::new(path, err.to_string()));
-            }
+/// Gets the modlog page by page until every table reaches the cursor, merging the pages.
+async fn modlog_cursor_get(
+    client: &Client,
+    cursor: &ModlogCursor,
+) -> Result<GetModlogResponse, ClientError> {
+    let query = ModlogQuery::new();
+    let mut modlog = modlog_get(client, &query).await?;
 
-            // Filter Post Removals
-            if let Err(err) =
-                get_post_removals(client, modlog.removed_posts, since, &mut actions.removals).await
-            {
-                return Err(ClientError::new(path, err.to_string()));
-            }
+    // Establishing the cursor on the first run only needs the most recent entries
+    if cursor.is_empty() {
+        return Ok(modlog);
+    }
 
-            Ok(actions)
+    let mut last = modlog_ids(&modlog);
+    for page in 2..=MAX_PAGES {
+        // A table is caught up once it runs out of entries or reaches an entry seen before
+        let is_caught_up = last.iter().all(|(kind, ids)| {
+            ids.len() < query.limit as usize || ids.iter().any(|id| !cursor.is_new(*kind, *id))
+        });
+        if is_caught_up {
+            break;
         }
-        status => Err(ClientError::new(path, status.to_string())),
+
+        let next = modlog_get(client, &query.clone().page(page)).await?;
+        last = modlog_ids(&next);
+        modlog.removed_posts.extend(next.removed_posts);
+        modlog.locked_posts.extend(next.locked_posts);
+        modlog.featured_posts.extend(next.featured_posts);
+        modlog.removed_comments.extend(next.removed_comments);
+        modlog.removed_communities.extend(next.removed_communities);
+        modlog
+            .banned_from_community
+            .extend(next.banned_from_community);
+        modlog.banned.extend(next.banned);
+        modlog.added_to_community.extend(next.added_to_community);
+        modlog
+            .transferred_to_community
+            .extend(next.transferred_to_community);
+        modlog.added.extend(next.added);
+        modlog
+            .admin_purged_persons
+            .extend(next.admin_purged_persons);
+        modlog
+            .admin_purged_communities
+            .extend(next.admin_purged_communities);
+        modlog.admin_purged_posts.extend(next.admin_purged_posts);
+        modlog
+            .admin_purged_comments
+            .extend(next.admin_purged_comments);
+        modlog.hidden_communities.extend(next.hidden_communities);
+    }
+
+    Ok(modlog)
+}
+
+/// Moves a cursor past every entry of a response, including tables without any entries.
+fn modlog_cursor(cursor: &ModlogCursor, modlog: &GetModlogResponse) -> ModlogCursor {
+    let mut cursor = cursor.clone();
+    for (kind, ids) in modlog_ids(modlog) {
+        cursor.advance(kind, ids.into_iter().max().unwrap_or_default());
+    }
+    cursor
+}
+
+/// Which moderators the bans and removals are collected for.
+#[derive(Clone, Copy, PartialEq)]
+enum Origin {
+    /// Remote moderators acting against local users
+    Remote,
+    /// Local moderators acting against any user
+    Local,
+}
+
+impl Origin {
+    fn includes_user(&self, user: &Person) -> bool {
+        *self == Origin::Local || user.is_local
+    }
+
+    fn includes_moderator(&self, moderator: &Person) -> bool {
+        moderator.is_local == (*self == Origin::Local)
     }
 }
 
 async fn get_site_bans(
     client: &Client,
     views: Vec<ModBanView>,
-    since: DateTime<Utc>,
+    cursor: &ModlogCursor,
+    origin: Origin,
     actions: &mut Vec<ModlogBan>,
 ) -> Result<(), ClientError> {
     for view in views {
-        // Filter actions that are older than the given timespan
-        let timestamp = view.mod_ban.when_;
-        if since <= timestamp {
-            // Filter any users that are not local
+        // Filter actions that have already been processed
+        if cursor.is_new(ModlogKind::SiteBan, view.mod_ban.id) {
+            // Filter any users outside of the origin
             let banned_user = Person::from(view.banned_person);
-            if !banned_user.is_local {
+            if !origin.includes_user(&banned_user) {
                 continue;
             }
 
@@ -102,8 +765,8 @@ async fn get_site_bans(
             let mod_id = PersonRef::Id(view.mod_ban.mod_person_id.0);
             match person_get(client, mod_id).await {
                 Ok(mod_user) => {
-                    // Filter any moderators that are local
-                    if mod_user.is_local {
+                    // Filter any moderators from the other origin
+                    if !origin.includes_moderator(&mod_user) {
                         continue;
                     }
 
@@ -130,16 +793,16 @@ async fn get_site_bans(
 async fn get_community_bans(
     client: &Client,
     views: Vec<ModBanFromCommunityView>,
-    since: DateTime<Utc>,
+    cursor: &ModlogCursor,
+    origin: Origin,
     actions: &mut Vec<ModlogBan>,
 ) -> Result<(), ClientError> {
     for view in views {
-        // Filter actions that are older than the given timespan
-        let timestamp = view.mod_ban_from_community.when_;
-        if since <= timestamp {
-            // Filter any users that are not local
+        // Filter actions that have already been processed
+        if cursor.is_new(ModlogKind::CommunityBan, view.mod_ban_from_community.id) {
+            // Filter any users outside of the origin
             let banned_user = Person::from(view.banned_person);
-            if !banned_user.is_local {
+            if !origin.includes_user(&banned_user) {
                 continue;
             }
 
@@ -147,8 +810,8 @@ async fn get_community_bans(
             let mod_id = PersonRef::Id(view.mod_ban_from_community.mod_person_id.0);
             match person_get(client, mod_id).await {
                 Ok(mod_user) => {
-                    // Filter any moderators that are local
-                    if mod_user.is_local {
+                    // Filter any moderators from the other origin
+                    if !origin.includes_moderator(&mod_user) {
                         continue;
                     }
 
@@ -176,16 +839,16 @@ async fn get_community_bans(
 async fn get_comment_removals(
     client: &Client,
     views: Vec<ModRemoveCommentView>,
-    since: DateTime<Utc>,
+    cursor: &ModlogCursor,
+    origin: Origin,
     actions: &mut Vec<ModlogRemoval>,
 ) -> Result<(), ClientError> {
     for view in views {
-        // Filter actions that are older than the given timespan
-        let timestamp = view.mod_remove_comment.when_;
-        if since <= timestamp {
-            // Filter any users that are not local
+        // Filter actions that have already been processed
+        if cursor.is_new(ModlogKind::CommentRemoval, view.mod_remove_comment.id) {
+            // Filter any users outside of the origin
             let commenter = Person::from(view.commenter);
-            if !commenter.is_local {
+            if !origin.includes_user(&commenter) {
                 continue;
             }
 
@@ -193,8 +856,8 @@ async fn get_comment_removals(
             let mod_id = PersonRef::Id(view.mod_remove_comment.mod_person_id.0);
             match person_get(client, mod_id).await {
                 Ok(mod_user) => {
-                    // Filter any moderators that are local
-                    if mod_user.is_local {
+                    // Filter any moderators from the other origin
+                    if !origin.includes_moderator(&mod_user) {
                         continue;
                     }
 
@@ -203,6 +866,7 @@ async fn get_comment_removals(
                         moderator: mod_user,
                         user: commenter,
                         comment: Comment::from(view.comment),
+                        community: Community::from(view.community),
                         is_removed: view.mod_remove_comment.removed,
                         reason: view.mod_remove_comment.reason,
                     };
@@ -221,14 +885,14 @@ async fn get_comment_removals(
 async fn get_post_removals(
     client: &Client,
     views: Vec<ModRemovePostView>,
-    since: DateTime<Utc>,
+    cursor: &ModlogCursor,
+    origin: Origin,
     actions: &mut Vec<ModlogRemoval>,
 ) -> Result<(), ClientError> {
     for view in views {
-        // Filter actions that are older than the given timespan
-        let timestamp = view.mod_remove_post.when_;
-        if since <= timestamp {
-            // Filter any users that are not local
+        // Filter actions that have already been processed
+        if cursor.is_new(ModlogKind::PostRemoval, view.mod_remove_post.id) {
+            // Filter any users outside of the origin
             let user_id = PersonRef::Id(view.post.creator_id.0);
             let poster = match person_get(client, user_id).await {
                 Ok(user) => user,
@@ -236,7 +900,7 @@ async fn get_post_removals(
                     return Err(err);
                 }
             };
-            if !poster.is_local {
+            if !origin.includes_user(&poster) {
                 continue;
             }
 
@@ -244,8 +908,8 @@ async fn get_post_removals(
             let mod_id = PersonRef::Id(view.mod_remove_post.mod_person_id.0);
             match person_get(client, mod_id).await {
                 Ok(mod_user) => {
-                    // Filter any moderators that are local
-                    if mod_user.is_local {
+                    // Filter any moderators from the other origin
+                    if !origin.includes_moderator(&mod_user) {
                         continue;
                     }
 
@@ -254,6 +918,7 @@ async fn get_post_removals(
                         moderator: mod_user,
                         user: poster,
                         post: Post::from(view.post),
+                        community: Community::from(view.community),
                         is_removed: view.mod_remove_post.removed,
                         reason: view.mod_remove_post.reason,
                     };
@@ -268,3 +933,292 @@ async fn get_post_removals(
 
     Ok(())
 }
+
+fn get_community_removals(
+    views: Vec<ModRemoveCommunityView>,
+    cursor: &ModlogCursor,
+    actions: &mut Vec<ModlogCommunityRemoval>,
+) {
+    for view in views {
+        // Filter actions that have already been processed
+        if cursor.is_new(ModlogKind::CommunityRemoval, view.mod_remove_community.id) {
+            // Filter any communities that are not local
+            let community = Community::from(view.community);
+            if !community.is_local {
+                continue;
+            }
+
+            // Skip entries where the moderator is hidden
+            let mod_user = match view.moderator {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+
+            // Create and append mod action
+            let action = ModlogCommunityRemoval {
+                moderator: mod_user,
+                community,
+                is_removed: view.mod_remove_community.removed,
+                reason: view.mod_remove_community.reason,
+            };
+            actions.push(action);
+        }
+    }
+}
+
+fn get_post_locks(
+    views: Vec<ModLockPostView>,
+    cursor: &ModlogCursor,
+    actions: &mut Vec<ModlogLock>,
+) {
+    for view in views {
+        // Filter actions that have already been processed
+        if cursor.is_new(ModlogKind::PostLock, view.mod_lock_post.id) {
+            // Filter any posts that are not local
+            if !view.post.local {
+                continue;
+            }
+
+            // Skip entries where the moderator is hidden
+            let mod_user = match view.moderator {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+
+            // Create and append mod action
+            let action = ModlogLock {
+                moderator: mod_user,
+                post: Post::from(view.post),
+                community: Community::from(view.community),
+                is_locked: view.mod_lock_post.locked,
+            };
+            actions.push(action);
+        }
+    }
+}
+
+fn get_post_features(
+    views: Vec<ModFeaturePostView>,
+    cursor: &ModlogCursor,
+    actions: &mut Vec<ModlogFeature>,
+) {
+    for view in views {
+        // Filter actions that have already been processed
+        if cursor.is_new(ModlogKind::PostFeature, view.mod_feature_post.id) {
+            // Filter any posts that are not local
+            if !view.post.local {
+                continue;
+            }
+
+            // Skip entries where the moderator is hidden
+            let mod_user = match view.moderator {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+
+            // Create and append mod action
+            let action = ModlogFeature {
+                moderator: mod_user,
+                post: Post::from(view.post),
+                community: Community::from(view.community),
+                is_featured: view.mod_feature_post.featured,
+                is_community: view.mod_feature_post.is_featured_community,
+            };
+            actions.push(action);
+        }
+    }
+}
+
+fn get_admin_adds(views: Vec<ModAddView>, cursor: &ModlogCursor, actions: &mut Vec<ModlogModAdd>) {
+    for view in views {
+        // Filter actions that have already been processed
+        if cursor.is_new(ModlogKind::AdminAdd, view.mod_add.id) {
+            // Filter any users that are not local
+            let user = Person::from(view.modded_person);
+            if !user.is_local {
+                continue;
+            }
+
+            // Skip entries where the moderator is hidden
+            let mod_user = match view.moderator {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+
+            // Create and append mod action
+            let action = ModlogModAdd::Site {
+                moderator: mod_user,
+                user,
+                is_added: !view.mod_add.removed,
+            };
+            actions.push(action);
+        }
+    }
+}
+
+fn get_mod_adds(
+    views: Vec<ModAddCommunityView>,
+    cursor: &ModlogCursor,
+    actions: &mut Vec<ModlogModAdd>,
+) {
+    for view in views {
+        // Filter actions that have already been processed
+        if cursor.is_new(ModlogKind::ModAdd, view.mod_add_community.id) {
+            // Filter any communities that are not local
+            let community = Community::from(view.community);
+            if !community.is_local {
+                continue;
+            }
+
+            // Skip entries where the moderator is hidden
+            let mod_user = match view.moderator {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+
+            // Create and append mod action
+            let action = ModlogModAdd::Community {
+                moderator: mod_user,
+                user: Person::from(view.modded_person),
+                community,
+                is_added: !view.mod_add_community.removed,
+            };
+            actions.push(action);
+        }
+    }
+}
+
+fn get_community_transfers(
+    views: Vec<ModTransferCommunityView>,
+    cursor: &ModlogCursor,
+    actions: &mut Vec<ModlogTransfer>,
+) {
+    for view in views {
+        // Filter actions that have already been processed
+        if cursor.is_new(
+            ModlogKind::CommunityTransfer,
+            view.mod_transfer_community.id,
+        ) {
+            // Filter any communities that are not local
+            let community = Community::from(view.community);
+            if !community.is_local {
+                continue;
+            }
+
+            // Skip entries where the moderator is hidden
+            let mod_user = match view.moderator {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+
+            // Create and append mod action
+            let action = ModlogTransfer {
+                moderator: mod_user,
+                user: Person::from(view.modded_person),
+                community,
+            };
+            actions.push(action);
+        }
+    }
+}
+
+fn get_community_hides(
+    views: Vec<ModHideCommunityView>,
+    cursor: &ModlogCursor,
+    actions: &mut Vec<ModlogHide>,
+) {
+    for view in views {
+        // Filter actions that have already been processed
+        if cursor.is_new(ModlogKind::CommunityHide, view.mod_hide_community.id) {
+            // Filter any communities that are not local
+            let community = Community::from(view.community);
+            if !community.is_local {
+                continue;
+            }
+
+            // Skip entries where the admin is hidden
+            let mod_user = match view.admin {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+
+            // Create and append mod action
+            let action = ModlogHide {
+                moderator: mod_user,
+                community,
+                is_hidden: view.mod_hide_community.hidden,
+                reason: view.mod_hide_community.reason,
+            };
+            actions.push(action);
+        }
+    }
+}
+
+struct Purges {
+    persons: Vec<AdminPurgePersonView>,
+    communities: Vec<AdminPurgeCommunityView>,
+    posts: Vec<AdminPurgePostView>,
+    comments: Vec<AdminPurgeCommentView>,
+}
+
+fn get_purges(views: Purges, cursor: &ModlogCursor, actions: &mut Vec<ModlogPurge>) {
+    // Purged content no longer exists, so every unprocessed purge is kept regardless of origin,
+    // unless the admin is hidden
+    for view in views.persons {
+        let purge = view.admin_purge_person;
+        if cursor.is_new(ModlogKind::PersonPurge, purge.id) {
+            let admin = match view.admin {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+            actions.push(ModlogPurge::Person {
+                admin,
+                reason: purge.reason,
+            });
+        }
+    }
+
+    for view in views.communities {
+        let purge = view.admin_purge_community;
+        if cursor.is_new(ModlogKind::CommunityPurge, purge.id) {
+            let admin = match view.admin {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+            actions.push(ModlogPurge::Community {
+                admin,
+                reason: purge.reason,
+            });
+        }
+    }
+
+    for view in views.posts {
+        let purge = view.admin_purge_post;
+        if cursor.is_new(ModlogKind::PostPurge, purge.id) {
+            let admin = match view.admin {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+            actions.push(ModlogPurge::Post {
+                admin,
+                community: Community::from(view.community),
+                reason: purge.reason,
+            });
+        }
+    }
+
+    for view in views.comments {
+        let purge = view.admin_purge_comment;
+        if cursor.is_new(ModlogKind::CommentPurge, purge.id) {
+            let admin = match view.admin {
+                Some(person) => Person::from(person),
+                None => continue,
+            };
+            actions.push(ModlogPurge::Comment {
+                admin,
+                post: Post::from(view.post),
+                reason: purge.reason,
+            });
+        }
+    }
+}
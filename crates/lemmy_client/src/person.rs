@@ -1,9 +1,11 @@
-use crate::endpoints::{ADMIN_PURGE_USER, USER, USER_BAN};
-use crate::model::Person;
+use crate::endpoints::{ADMIN_PURGE_USER, USER, USER_BAN, USER_BLOCK};
+use crate::model::{Community, Person};
 use crate::{Client, ClientError};
 use chrono::{DateTime, Utc};
 use lemmy_api_common::lemmy_db_schema::newtypes::PersonId;
-use lemmy_api_common::person::{BanPerson, GetPersonDetails, GetPersonDetailsResponse};
+use lemmy_api_common::person::{
+    BanPerson, BlockPerson, GetPersonDetails, GetPersonDetailsResponse,
+};
 use lemmy_api_common::site::PurgePerson;
 use reqwest::StatusCode;
 
@@ -49,6 +51,44 @@ pub async fn person_get(client: &Client, person_ref: PersonRef) -> Result<Person
     }
 }
 
+pub async fn person_moderates_get(
+    client: &Client,
+    person_id: i32,
+) -> Result<Vec<Community>, ClientError> {
+    // Create and perform request
+    let path = USER;
+    let params = GetPersonDetails {
+        person_id: Some(PersonId(person_id)),
+        ..Default::default()
+    };
+    let result = client.get(path, false).query(&params).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => {
+            // Parse response body
+            let body = response.json::<GetPersonDetailsResponse>().await;
+            if let Err(err) = body {
+                return Err(ClientError::new(path, err.to_string()));
+            }
+            let person = body.ok().unwrap();
+
+            let communities = person
+                .moderates
+                .iter()
+                .map(|view| Community::from(view.community.clone()))
+                .collect::<Vec<Community>>();
+
+            Ok(communities)
+        }
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
+
 pub async fn person_ban(
     client: &Client,
     person_id: i32,
@@ -106,3 +146,23 @@ pub async fn person_purge(
         status => Err(ClientError::new(path, status.to_string())),
     }
 }
+
+pub async fn person_block(client: &Client, person_id: i32, block: bool) -> Result<(), ClientError> {
+    // Create and perform request
+    let path = USER_BLOCK;
+    let body = BlockPerson {
+        person_id: PersonId(person_id),
+        block,
+    };
+    let result = client.post(path, true).json(&body).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
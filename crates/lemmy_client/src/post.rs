@@ -1,7 +1,8 @@
-use crate::endpoints::POST_REPORT;
+use crate::endpoints::{POST_FEATURE, POST_LOCK, POST_REMOVE, POST_REPORT};
 use crate::{Client, ClientError};
 use lemmy_api_common::lemmy_db_schema::newtypes::PostId;
-use lemmy_api_common::post::CreatePostReport;
+use lemmy_api_common::lemmy_db_schema::PostFeatureType;
+use lemmy_api_common::post::{CreatePostReport, FeaturePost, LockPost, RemovePost};
 use reqwest::StatusCode;
 
 pub async fn post_report(client: &Client, post_id: i32, reason: String) -> Result<(), ClientError> {
@@ -23,3 +24,78 @@ pub async fn post_report(client: &Client, post_id: i32, reason: String) -> Resul
         status => Err(ClientError::new(path, status.to_string())),
     }
 }
+
+pub async fn post_remove(
+    client: &Client,
+    post_id: i32,
+    removed: bool,
+    reason: Option<String>,
+) -> Result<(), ClientError> {
+    // Create and perform request
+    let path = POST_REMOVE;
+    let body = RemovePost {
+        post_id: PostId(post_id),
+        removed,
+        reason,
+    };
+    let result = client.post(path, true).json(&body).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
+
+pub async fn post_lock(client: &Client, post_id: i32, locked: bool) -> Result<(), ClientError> {
+    // Create and perform request
+    let path = POST_LOCK;
+    let body = LockPost {
+        post_id: PostId(post_id),
+        locked,
+    };
+    let result = client.post(path, true).json(&body).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
+
+pub async fn post_feature(
+    client: &Client,
+    post_id: i32,
+    featured: bool,
+    is_community: bool,
+) -> Result<(), ClientError> {
+    // Create and perform request
+    let path = POST_FEATURE;
+    let body = FeaturePost {
+        post_id: PostId(post_id),
+        featured,
+        feature_type: match is_community {
+            true => PostFeatureType::Community,
+            false => PostFeatureType::Local,
+        },
+    };
+    let result = client.post(path, true).json(&body).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
@@ -1,12 +1,13 @@
 use crate::endpoints::{
     PRIVATE_MESSAGE, PRIVATE_MESSAGE_DELETE, PRIVATE_MESSAGE_LIST, PRIVATE_MESSAGE_READ,
+    PRIVATE_MESSAGE_REPORT,
 };
 use crate::model::PrivateMessage;
 use crate::{Client, ClientError};
 use lemmy_api_common::lemmy_db_schema::newtypes::{PersonId, PrivateMessageId};
 use lemmy_api_common::private_message::{
-    CreatePrivateMessage, DeletePrivateMessage, GetPrivateMessages, MarkPrivateMessageAsRead,
-    PrivateMessageResponse, PrivateMessagesResponse,
+    CreatePrivateMessage, CreatePrivateMessageReport, DeletePrivateMessage, GetPrivateMessages,
+    MarkPrivateMessageAsRead, PrivateMessageResponse, PrivateMessagesResponse,
 };
 use reqwest::StatusCode;
 
@@ -71,11 +72,13 @@ pub async fn private_message_delete(
 pub async fn private_message_list(
     client: &Client,
     unread: bool,
+    page: i64,
 ) -> Result<Vec<PrivateMessage>, ClientError> {
     // Create and perform request
     let path = PRIVATE_MESSAGE_LIST;
     let body = GetPrivateMessages {
         unread_only: Some(unread),
+        page: Some(page),
         limit: Some(50),
         ..Default::default()
     };
@@ -129,3 +132,27 @@ pub async fn private_message_read(
         status => Err(ClientError::new(path, status.to_string())),
     }
 }
+
+pub async fn private_message_report(
+    client: &Client,
+    message_id: PrivateMessageId,
+    reason: String,
+) -> Result<(), ClientError> {
+    // Create and perform request
+    let path = PRIVATE_MESSAGE_REPORT;
+    let body = CreatePrivateMessageReport {
+        private_message_id: message_id,
+        reason,
+    };
+    let result = client.post(path, true).json(&body).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
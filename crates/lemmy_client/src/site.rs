@@ -1,7 +1,7 @@
-use crate::endpoints::SITE;
-use crate::model::Person;
+use crate::endpoints::{RESOLVE_OBJECT, SITE};
+use crate::model::{Comment, Community, Object, Person, Post};
 use crate::{Client, ClientError};
-use lemmy_api_common::site::GetSiteResponse;
+use lemmy_api_common::site::{GetSiteResponse, ResolveObject, ResolveObjectResponse};
 use reqwest::StatusCode;
 
 pub async fn site_admins_get(client: &Client) -> Result<Vec<Person>, ClientError> {
@@ -35,3 +35,43 @@ pub async fn site_admins_get(client: &Client) -> Result<Vec<Person>, ClientError
         status => Err(ClientError::new(path, status.to_string())),
     }
 }
+
+pub async fn resolve_object(client: &Client, query: String) -> Result<Object, ClientError> {
+    // Create and perform request, federated objects are only fetched when authenticated
+    let path = RESOLVE_OBJECT;
+    let params = ResolveObject { q: query };
+    let result = client.get(path, true).query(&params).send().await;
+    if let Err(err) = result {
+        return Err(ClientError::new(path, err.to_string()));
+    }
+
+    // Validate response status
+    let response = result.ok().unwrap();
+    match response.status() {
+        StatusCode::OK => {
+            // Parse response body
+            let body = response.json::<ResolveObjectResponse>().await;
+            if let Err(err) = body {
+                return Err(ClientError::new(path, err.to_string()));
+            }
+            let object = body.ok().unwrap();
+
+            if let Some(view) = object.comment {
+                let community = Community::from(view.community);
+                return Ok(Object::Comment(Comment::from(view.comment), community));
+            }
+            if let Some(view) = object.post {
+                let community = Community::from(view.community);
+                return Ok(Object::Post(Post::from(view.post), community));
+            }
+            if let Some(view) = object.community {
+                return Ok(Object::Community(Community::from(view.community)));
+            }
+            match object.person {
+                Some(view) => Ok(Object::Person(Person::from(view.person))),
+                None => Err(ClientError::new(path, "object not found".to_string())),
+            }
+        }
+        status => Err(ClientError::new(path, status.to_string())),
+    }
+}
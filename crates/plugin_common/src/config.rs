@@ -0,0 +1,104 @@
+use crate::notifier::{Event, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct Templates {
+    pub directory: Option<String>,
+    pub locale: String,
+    pub messages: HashMap<String, String>,
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        Templates {
+            directory: None,
+            locale: "en".to_string(),
+            messages: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct Notifications {
+    pub digest_interval: i64,
+    pub suppression_window: i64,
+    pub max_attempts: u32,
+    pub retry_interval: i64,
+    pub routes: Vec<Route>,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Notifications {
+            digest_interval: 86400,
+            suppression_window: 3600,
+            max_attempts: 5,
+            retry_interval: 60,
+            routes: vec![Route {
+                roles: vec![Role::Admins],
+                ..Default::default()
+            }],
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Route {
+    pub name: Option<String>,
+    pub events: Vec<Event>,
+    pub min_severity: Severity,
+    pub roles: Vec<Role>,
+    pub users: Vec<String>,
+    pub communities: Vec<String>,
+    pub webhooks: Vec<String>,
+    pub push: Vec<Push>,
+    pub digest: bool,
+    pub quiet_hours: Option<QuietHours>,
+}
+
+#[derive(Deserialize)]
+pub struct Push {
+    pub service: PushService,
+    pub url: String,
+    pub token: Option<String>,
+    #[serde(default)]
+    pub tags: HashMap<Event, Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PushService {
+    Ntfy,
+    Gotify,
+}
+
+#[derive(Deserialize)]
+pub struct QuietHours {
+    pub start: String,
+    pub end: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admins,
+    Moderators,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct State {
+    pub directory: String,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            directory: "state".to_string(),
+        }
+    }
+}
@@ -0,0 +1,831 @@
+use crate::config;
+use crate::config::{PushService, QuietHours, Role, Route};
+use crate::push::push_send;
+use crate::state::State;
+use crate::template::{Message, Templates, NOTIFICATION_DIGEST, PUSH_TITLE};
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use lemmy_client::community::community_moderators_get;
+use lemmy_client::model::Person;
+use lemmy_client::person::{person_get, PersonRef};
+use lemmy_client::private_message::private_message_create;
+use lemmy_client::site::site_admins_get;
+use lemmy_client::{Client, ClientError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use tracing::{debug, error, warn};
+
+const DIGEST_SEPARATOR: &str = "\r\n\r\n---\r\n\r\n";
+const MAX_RETRY_BACKOFF: i64 = 86400;
+const QUIET_HOURS_FORMAT: &str = "%H:%M";
+const OUTBOX: &str = "outbox";
+const REPLAY: &str = "outbox_replay";
+const STATE: &str = "notifications";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Event {
+    CommandAudit,
+    CommandDenied,
+    FederatedBan,
+    FederatedBanDecision,
+    FederatedBanPending,
+    FederatedBanProposal,
+    FederatedBanReport,
+    FederatedRemoval,
+    LocalModeratorConflict,
+    LocalModeratorRate,
+    MessageForward,
+    MessageQuarantine,
+    ModlogBan,
+    ModlogRemoval,
+}
+
+impl Event {
+    fn severity(&self) -> Severity {
+        match self {
+            Event::CommandAudit => Severity::Low,
+            Event::CommandDenied => Severity::Medium,
+            Event::FederatedBan => Severity::High,
+            Event::FederatedBanDecision => Severity::Medium,
+            Event::FederatedBanPending => Severity::Medium,
+            Event::FederatedBanProposal => Severity::High,
+            Event::FederatedBanReport => Severity::Low,
+            Event::FederatedRemoval => Severity::Medium,
+            Event::LocalModeratorConflict => Severity::Medium,
+            Event::LocalModeratorRate => Severity::High,
+            Event::MessageForward => Severity::Medium,
+            Event::MessageQuarantine => Severity::Low,
+            Event::ModlogBan => Severity::Medium,
+            Event::ModlogRemoval => Severity::Low,
+        }
+    }
+
+    /// Default push notification tags, ntfy renders these as emojis.
+    fn tags(&self) -> Vec<String> {
+        let tag = match self {
+            Event::CommandAudit => "robot",
+            Event::CommandDenied => "no_entry_sign",
+            Event::FederatedBan => "hammer",
+            Event::FederatedBanDecision => "ballot_box",
+            Event::FederatedBanPending => "hourglass",
+            Event::FederatedBanProposal => "raised_hand",
+            Event::FederatedBanReport => "clipboard",
+            Event::FederatedRemoval => "scissors",
+            Event::LocalModeratorConflict => "crossed_swords",
+            Event::LocalModeratorRate => "rotating_light",
+            Event::MessageForward => "envelope",
+            Event::MessageQuarantine => "mailbox_with_no_mail",
+            Event::ModlogBan => "no_entry",
+            Event::ModlogRemoval => "wastebasket",
+        };
+        vec![tag.to_string(), self.to_string()]
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Event::CommandAudit => "command_audit",
+            Event::CommandDenied => "command_denied",
+            Event::FederatedBan => "federated_ban",
+            Event::FederatedBanDecision => "federated_ban_decision",
+            Event::FederatedBanPending => "federated_ban_pending",
+            Event::FederatedBanProposal => "federated_ban_proposal",
+            Event::FederatedBanReport => "federated_ban_report",
+            Event::FederatedRemoval => "federated_removal",
+            Event::LocalModeratorConflict => "local_moderator_conflict",
+            Event::LocalModeratorRate => "local_moderator_rate",
+            Event::MessageForward => "message_forward",
+            Event::MessageQuarantine => "message_quarantine",
+            Event::ModlogBan => "modlog_ban",
+            Event::ModlogRemoval => "modlog_removal",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    #[default]
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+pub struct Notification {
+    event: Event,
+    severity: Severity,
+    community: Option<String>,
+    user: Option<String>,
+    instance: Option<String>,
+    message: Message,
+}
+
+impl Notification {
+    pub fn new(event: Event, message: Message) -> Self {
+        Notification {
+            event,
+            severity: event.severity(),
+            community: None,
+            user: None,
+            instance: None,
+            message,
+        }
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Community the notification relates to, used to resolve its moderators.
+    pub fn community(mut self, community: String) -> Self {
+        self.community = Some(community);
+        self
+    }
+
+    /// User the notification is about, used for snoozing.
+    pub fn user(mut self, user: &Person) -> Self {
+        self.user = Some(format!("{}@{}", user.name, user.instance));
+        self
+    }
+
+    /// Instance the notification is about, used for snoozing.
+    pub fn instance(mut self, instance: String) -> Self {
+        self.instance = Some(instance);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Snooze {
+    User(String),
+    Instance(String),
+}
+
+#[derive(Serialize, Deserialize)]
+struct Snoozed {
+    target: Snooze,
+    until: DateTime<Utc>,
+}
+
+/// Notification history persisted across runs.
+#[derive(Serialize, Deserialize, Default)]
+struct History {
+    sent: HashMap<String, DateTime<Utc>>,
+    snoozed: Vec<Snoozed>,
+}
+
+/// A rendered notification waiting to be delivered.
+#[derive(Serialize, Deserialize, Clone)]
+struct Delivery {
+    event: Event,
+    severity: Severity,
+    community: Option<String>,
+    body: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum Target {
+    PrivateMessage {
+        person_id: i32,
+        name: String,
+    },
+    /// Webhook URLs often contain secret tokens, so only their host and digest are persisted
+    Webhook {
+        #[serde(default)]
+        host: String,
+        #[serde(default)]
+        key: String,
+    },
+    Push {
+        service: PushService,
+        url: String,
+        title: String,
+        severity: Severity,
+        tags: Vec<String>,
+    },
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::PrivateMessage { name, .. } => write!(f, "private_message({})", name),
+            Target::Webhook { host, .. } => write!(f, "webhook({})", host),
+            Target::Push { url, .. } => write!(f, "push({})", url),
+        }
+    }
+}
+
+/// A notification addressed to a single target, retried until delivered.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Outbound {
+    pub id: u64,
+    pub target: Target,
+    pub body: String,
+    pub created: DateTime<Utc>,
+    pub attempts: u32,
+    pub next_attempt: DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+/// A routed notification held back by quiet hours or failed lookups.
+#[derive(Serialize, Deserialize)]
+struct Held {
+    route: String,
+    delivery: Delivery,
+}
+
+/// Notifications awaiting delivery, persisted across runs.
+///
+/// Routed notifications are keyed by route, see `route_key`, so they follow their route when the
+/// configuration is reordered and are dropped once it has been removed.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct Outbox {
+    next_id: u64,
+    last_digest: Option<DateTime<Utc>>,
+    pending: Vec<Delivery>,
+    held: Vec<Held>,
+    digests: HashMap<String, Vec<Delivery>>,
+    queue: Vec<Outbound>,
+    dead_letters: Vec<Outbound>,
+}
+
+/// Dead letters to replay, requested from the command line and picked up by the running bot.
+#[derive(Serialize, Deserialize, Default)]
+struct Replay {
+    all: bool,
+    ids: Vec<u64>,
+}
+
+pub struct Notifier {
+    config: config::Notifications,
+    keys: Vec<String>,
+    templates: Templates,
+    state: State,
+    history: History,
+    outbox: Outbox,
+    http: reqwest::Client,
+}
+
+impl Notifier {
+    pub fn new(config: config::Notifications, templates: Templates, state: State) -> Self {
+        // Validate quiet hours up front rather than on every delivery
+        for route in &config.routes {
+            if let Some(quiet_hours) = &route.quiet_hours {
+                if parse_quiet_hours(quiet_hours).is_none() {
+                    error!(
+                        "invalid quiet hours: {} - {}, expected format: {}",
+                        quiet_hours.start, quiet_hours.end, QUIET_HOURS_FORMAT
+                    );
+                }
+            }
+        }
+
+        // Identical keys would merge the held notifications and digests of their routes
+        let keys = config.routes.iter().map(route_key).collect::<Vec<String>>();
+        for (index, key) in keys.iter().enumerate() {
+            if keys[..index].contains(key) {
+                warn!("duplicate notification route: {}", key);
+            }
+        }
+
+        let history = state.load::<History>(STATE);
+        let outbox = state.load::<Outbox>(OUTBOX);
+
+        Notifier {
+            config,
+            keys,
+            templates,
+            state,
+            history,
+            outbox,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn render(&self, message: &Message) -> String {
+        self.templates.render(message)
+    }
+
+    /// Queues a notification, which will be routed and delivered on the next flush.
+    pub fn notify(&mut self, notification: Notification) {
+        let now = Utc::now();
+        let name = notification.message.name();
+
+        // Skip notifications about snoozed users or instances
+        self.history.snoozed.retain(|snoozed| snoozed.until > now);
+        let is_snoozed = self
+            .history
+            .snoozed
+            .iter()
+            .any(|snoozed| match &snoozed.target {
+                Snooze::User(user) => notification.user.as_ref() == Some(user),
+                Snooze::Instance(instance) => notification.instance.as_ref() == Some(instance),
+            });
+        if is_snoozed {
+            debug!("Snoozed notification: {}", name);
+            return;
+        }
+
+        // Skip notifications identical to one sent within the suppression window
+        let body = self.render(&notification.message);
+        let hash = format!("{:x}", Sha256::digest(body.as_bytes()));
+        let window = Duration::seconds(self.config.suppression_window);
+        self.history.sent.retain(|_, sent| now - *sent < window);
+        if self.history.sent.contains_key(&hash) {
+            debug!("Suppressed duplicate notification: {}", name);
+            return;
+        }
+        if !window.is_zero() {
+            self.history.sent.insert(hash, now);
+        }
+        self.state.save(STATE, &self.history);
+
+        debug!("Queued notification: {}", name);
+        self.outbox.pending.push(Delivery {
+            event: notification.event,
+            severity: notification.severity,
+            community: notification.community,
+            body,
+        });
+        self.state.save(OUTBOX, &self.outbox);
+    }
+
+    /// Suppresses any notifications about the given user or instance until the given time.
+    pub fn snooze(&mut self, target: Snooze, until: DateTime<Utc>) {
+        self.history
+            .snoozed
+            .retain(|snoozed| snoozed.target != target);
+        self.history.snoozed.push(Snoozed { target, until });
+        self.state.save(STATE, &self.history);
+    }
+
+    /// Deliveries which have exhausted their attempts.
+    pub fn dead_letters(&self) -> &[Outbound] {
+        &self.outbox.dead_letters
+    }
+
+    /// Requests the running bot to replay dead letters, either all of them or only the given ids.
+    ///
+    /// The outbox is owned by the running bot, so it is left untouched here.
+    pub fn request_replay(&self, ids: &[u64]) -> usize {
+        let mut replay = self.state.load::<Replay>(REPLAY);
+        replay.all |= ids.is_empty();
+        replay.ids.extend(ids);
+        self.state.save(REPLAY, &replay);
+
+        self.outbox
+            .dead_letters
+            .iter()
+            .filter(|outbound| ids.is_empty() || ids.contains(&outbound.id))
+            .count()
+    }
+
+    /// Moves dead letters back into the queue, either all of them or only the given ids.
+    fn replay(&mut self, ids: &[u64]) -> usize {
+        let now = Utc::now();
+        let (mut replayed, remaining) = std::mem::take(&mut self.outbox.dead_letters)
+            .into_iter()
+            .partition::<Vec<Outbound>, _>(|outbound| {
+            ids.is_empty() || ids.contains(&outbound.id)
+        });
+        self.outbox.dead_letters = remaining;
+
+        let count = replayed.len();
+        for outbound in replayed.iter_mut() {
+            outbound.attempts = 0;
+            outbound.next_attempt = now;
+        }
+        self.outbox.queue.append(&mut replayed);
+        self.state.save(OUTBOX, &self.outbox);
+
+        count
+    }
+
+    pub async fn flush(&mut self, client: &Client) {
+        let now = Utc::now();
+        let mut recipients = Recipients::default();
+
+        // Replay any dead letters requested from the command line
+        if let Some(replay) = self.state.take::<Replay>(REPLAY) {
+            let ids = match replay.all {
+                true => vec![],
+                false => replay.ids,
+            };
+            let count = self.replay(&ids);
+            debug!("Replaying {} failed deliveries", count);
+        }
+
+        // Route any queued notifications
+        for delivery in std::mem::take(&mut self.outbox.pending) {
+            let routes = self
+                .config
+                .routes
+                .iter()
+                .enumerate()
+                .filter(|(_, route)| route_matches(route, &delivery))
+                .map(|(index, _)| index)
+                .collect::<Vec<usize>>();
+
+            for index in routes {
+                let route = &self.config.routes[index];
+                let key = self.keys[index].clone();
+                let delivery = delivery.clone();
+
+                if route.digest {
+                    self.outbox.digests.entry(key).or_default().push(delivery);
+                } else if is_quiet(route, now) {
+                    self.outbox.held.push(Held {
+                        route: key,
+                        delivery,
+                    });
+                } else {
+                    self.dispatch(client, &mut recipients, key, delivery).await;
+                }
+            }
+        }
+
+        // Retry any deliveries held back by quiet hours or failed lookups
+        for held in std::mem::take(&mut self.outbox.held) {
+            let route = match self.route(&held.route) {
+                Some(value) => value,
+                None => {
+                    warn!("dropped notification for removed route: {}", held.route);
+                    continue;
+                }
+            };
+
+            if is_quiet(route, now) {
+                self.outbox.held.push(held);
+            } else {
+                self.dispatch(client, &mut recipients, held.route, held.delivery)
+                    .await;
+            }
+        }
+
+        // Send digests once scheduled
+        let last_digest = *self.outbox.last_digest.get_or_insert(now);
+        if (now - last_digest).num_seconds() >= self.config.digest_interval {
+            self.outbox.last_digest = Some(now);
+
+            for (key, deliveries) in std::mem::take(&mut self.outbox.digests) {
+                if deliveries.is_empty() {
+                    continue;
+                }
+
+                let notifications = deliveries
+                    .iter()
+                    .map(|delivery| delivery.body.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(DIGEST_SEPARATOR);
+                let message = Message::new(NOTIFICATION_DIGEST)
+                    .with("count", deliveries.len())
+                    .with("notifications", notifications);
+                let digest = Delivery {
+                    event: deliveries[0].event,
+                    severity: deliveries
+                        .iter()
+                        .map(|delivery| delivery.severity)
+                        .max()
+                        .unwrap_or_default(),
+                    community: None,
+                    body: self.render(&message),
+                };
+
+                self.dispatch(client, &mut recipients, key, digest).await;
+            }
+        }
+
+        // Attempt delivery of each queued notification which is due
+        for mut outbound in std::mem::take(&mut self.outbox.queue) {
+            if outbound.next_attempt > now {
+                self.outbox.queue.push(outbound);
+                continue;
+            }
+
+            let err = match self.send(client, &outbound).await {
+                Ok(_) => continue,
+                Err(err) => err,
+            };
+            error!("delivery failed: {} -> {}", outbound.target, err);
+
+            // Retry with exponential backoff, until attempts are exhausted
+            outbound.attempts += 1;
+            outbound.last_error = Some(err);
+            if outbound.attempts >= self.config.max_attempts {
+                error!(
+                    "delivery abandoned after {} attempts: {} -> {}",
+                    outbound.attempts, outbound.id, outbound.target
+                );
+                self.outbox.dead_letters.push(outbound);
+            } else {
+                let backoff = self
+                    .config
+                    .retry_interval
+                    .saturating_mul(2_i64.saturating_pow(outbound.attempts - 1))
+                    .clamp(0, MAX_RETRY_BACKOFF);
+                outbound.next_attempt = now
+                    .checked_add_signed(Duration::seconds(backoff))
+                    .unwrap_or(now);
+                self.outbox.queue.push(outbound);
+            }
+        }
+
+        self.state.save(OUTBOX, &self.outbox);
+    }
+
+    /// Resolves the recipients of a route, queueing the delivery for each of them.
+    async fn dispatch(
+        &mut self,
+        client: &Client,
+        recipients: &mut Recipients,
+        key: String,
+        delivery: Delivery,
+    ) {
+        let route = match self.keys.iter().position(|value| *value == key) {
+            Some(index) => &self.config.routes[index],
+            None => {
+                warn!("dropped notification for removed route: {}", key);
+                return;
+            }
+        };
+        let persons = match recipients
+            .resolve(client, route, delivery.community.as_ref())
+            .await
+        {
+            Ok(value) => value,
+            Err(err) => {
+                // Try again on the next flush, recipients may be available by then
+                error!("{}", err);
+                self.outbox.held.push(Held {
+                    route: key,
+                    delivery,
+                });
+                return;
+            }
+        };
+
+        let title = self.render(
+            &Message::new(PUSH_TITLE)
+                .with("event", delivery.event)
+                .with("severity", delivery.severity),
+        );
+        let pushes = route.push.iter().map(|push| Target::Push {
+            service: push.service.clone(),
+            url: push.url.clone(),
+            title: title.clone(),
+            severity: delivery.severity,
+            tags: match push.tags.get(&delivery.event) {
+                Some(tags) => tags.clone(),
+                None => delivery.event.tags(),
+            },
+        });
+
+        let now = Utc::now();
+        let targets = persons
+            .into_iter()
+            .map(|person| Target::PrivateMessage {
+                person_id: person.id,
+                name: format!("{}@{}", person.name, person.instance),
+            })
+            .chain(route.webhooks.iter().map(|url| {
+                Target::Webhook {
+                    host: reqwest::Url::parse(url)
+                        .ok()
+                        .and_then(|url| url.host_str().map(str::to_string))
+                        .unwrap_or_default(),
+                    key: webhook_key(url),
+                }
+            }))
+            .chain(pushes);
+        for target in targets {
+            self.outbox.next_id += 1;
+            self.outbox.queue.push(Outbound {
+                id: self.outbox.next_id,
+                target,
+                body: delivery.body.clone(),
+                created: now,
+                attempts: 0,
+                next_attempt: now,
+                last_error: None,
+            });
+        }
+    }
+
+    fn route(&self, key: &str) -> Option<&Route> {
+        let index = self.keys.iter().position(|value| value == key)?;
+        self.config.routes.get(index)
+    }
+
+    async fn send(&self, client: &Client, outbound: &Outbound) -> Result<(), String> {
+        match &outbound.target {
+            Target::PrivateMessage { person_id, .. } => {
+                private_message_create(client, *person_id, outbound.body.clone())
+                    .await
+                    .map(|_| ())
+                    .map_err(|err| err.to_string())
+            }
+            Target::Webhook { key, .. } => {
+                // Look up the current configuration, so webhook URLs are never persisted
+                let url = self
+                    .config
+                    .routes
+                    .iter()
+                    .flat_map(|route| &route.webhooks)
+                    .find(|url| webhook_key(url) == *key)
+                    .ok_or("webhook target is no longer configured".to_string())?;
+
+                let body = HashMap::from([("content", &outbound.body), ("text", &outbound.body)]);
+                match self.http.post(url).json(&body).send().await {
+                    Ok(response) if !response.status().is_success() => {
+                        Err(response.status().to_string())
+                    }
+                    Ok(_) => Ok(()),
+                    Err(err) => Err(err.to_string()),
+                }
+            }
+            Target::Push {
+                url,
+                title,
+                severity,
+                tags,
+                ..
+            } => {
+                // Look up the current configuration, so credentials are never persisted
+                let push = self
+                    .config
+                    .routes
+                    .iter()
+                    .flat_map(|route| &route.push)
+                    .find(|push| push.url == *url);
+                match push {
+                    Some(push) => {
+                        push_send(&self.http, push, title, &outbound.body, *severity, tags).await
+                    }
+                    None => Err("push target is no longer configured".to_string()),
+                }
+            }
+        }
+    }
+}
+
+/// Caches recipient lookups for the duration of a single flush.
+#[derive(Default)]
+struct Recipients {
+    admins: Option<Vec<Person>>,
+    users: HashMap<String, Option<Person>>,
+    moderators: HashMap<String, Vec<Person>>,
+}
+
+impl Recipients {
+    async fn resolve(
+        &mut self,
+        client: &Client,
+        route: &Route,
+        community: Option<&String>,
+    ) -> Result<Vec<&Person>, ClientError> {
+        if route.roles.contains(&Role::Admins) && self.admins.is_none() {
+            self.admins = Some(site_admins_get(client).await?);
+        }
+
+        for username in &route.users {
+            if self.users.contains_key(username) {
+                continue;
+            }
+
+            // Skip unknown users rather than holding back the whole delivery
+            let person = match person_get(client, PersonRef::Username(username.clone())).await {
+                Ok(person) => Some(person),
+                Err(err) => {
+                    error!("{}", err);
+                    None
+                }
+            };
+            self.users.insert(username.clone(), person);
+        }
+
+        let mut communities = route.communities.iter().collect::<Vec<&String>>();
+        if route.roles.contains(&Role::Moderators) {
+            match community {
+                Some(community) => communities.push(community),
+                None => warn!("unable to notify moderators without a community"),
+            }
+        }
+        for name in &communities {
+            if self.moderators.contains_key(*name) {
+                continue;
+            }
+
+            // Skip unknown communities rather than holding back the whole delivery
+            let moderators = match community_moderators_get(client, name.to_string()).await {
+                Ok(moderators) => moderators,
+                Err(err) => {
+                    error!("{}", err);
+                    vec![]
+                }
+            };
+            self.moderators.insert(name.to_string(), moderators);
+        }
+
+        // Collect each unique recipient, skipping the bot itself
+        let mut ids = HashSet::from([client.user_id()]);
+        let mut persons = vec![];
+        let admins = match route.roles.contains(&Role::Admins) {
+            true => self.admins.iter().flatten().collect::<Vec<&Person>>(),
+            false => vec![],
+        };
+        let users = route
+            .users
+            .iter()
+            .filter_map(|username| self.users.get(username).and_then(Option::as_ref));
+        let moderators = communities
+            .iter()
+            .filter_map(|name| self.moderators.get(*name))
+            .flatten();
+        for person in admins.into_iter().chain(users).chain(moderators) {
+            if ids.insert(person.id) {
+                persons.push(person);
+            }
+        }
+
+        Ok(persons)
+    }
+}
+
+fn webhook_key(url: &str) -> String {
+    format!("{:x}", Sha256::digest(url.as_bytes()))
+}
+
+/// Identifies a route across restarts by its name, or by its settings when unnamed.
+fn route_key(route: &Route) -> String {
+    if let Some(name) = &route.name {
+        return name.clone();
+    }
+
+    // Push credentials and tags don't affect which notifications a route delivers to whom
+    let settings = (
+        &route.events,
+        route.min_severity,
+        &route.roles,
+        &route.users,
+        &route.communities,
+        &route.webhooks,
+        route
+            .push
+            .iter()
+            .map(|push| &push.url)
+            .collect::<Vec<&String>>(),
+        route.digest,
+        route
+            .quiet_hours
+            .as_ref()
+            .map(|quiet_hours| (&quiet_hours.start, &quiet_hours.end)),
+    );
+    let settings = serde_json::to_string(&settings).unwrap_or_default();
+    format!("{:x}", Sha256::digest(settings.as_bytes()))
+}
+
+fn route_matches(route: &Route, delivery: &Delivery) -> bool {
+    (route.events.is_empty() || route.events.contains(&delivery.event))
+        && delivery.severity >= route.min_severity
+}
+
+fn parse_quiet_hours(quiet_hours: &QuietHours) -> Option<(NaiveTime, NaiveTime)> {
+    let start = NaiveTime::parse_from_str(&quiet_hours.start, QUIET_HOURS_FORMAT).ok()?;
+    let end = NaiveTime::parse_from_str(&quiet_hours.end, QUIET_HOURS_FORMAT).ok()?;
+    Some((start, end))
+}
+
+fn is_quiet(route: &Route, now: DateTime<Utc>) -> bool {
+    let (start, end) = match route.quiet_hours.as_ref().and_then(parse_quiet_hours) {
+        Some(value) => value,
+        None => return false,
+    };
+
+    // Quiet hours may wrap around midnight
+    let time = now.time();
+    match start <= end {
+        true => start <= time && time < end,
+        false => start <= time || time < end,
+    }
+}
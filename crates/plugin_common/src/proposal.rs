@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+pub const STATE: &str = "proposals";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Pending,
+    Approved,
+    Rejected,
+    Expired,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Status::Pending => "pending",
+            Status::Approved => "approved",
+            Status::Rejected => "rejected",
+            Status::Expired => "expired",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A federated ban awaiting approval from an admin.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Proposal {
+    pub id: u64,
+    pub user_id: i32,
+    pub user: String,
+    pub reason: String,
+    pub expires: Option<DateTime<Utc>>,
+    pub created: DateTime<Utc>,
+    pub status: Status,
+    pub resolved: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Decision {
+    pub proposal_id: u64,
+    pub status: Status,
+    pub by: String,
+    pub when: DateTime<Utc>,
+}
+
+/// Proposals and the audit trail of decisions made on them, shared between plugins.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Proposals {
+    next_id: u64,
+    pub proposals: Vec<Proposal>,
+    pub audit: Vec<Decision>,
+}
+
+impl Proposals {
+    pub fn propose(
+        &mut self,
+        user_id: i32,
+        user: String,
+        reason: String,
+        expires: Option<DateTime<Utc>>,
+    ) -> u64 {
+        self.next_id += 1;
+        self.proposals.push(Proposal {
+            id: self.next_id,
+            user_id,
+            user,
+            reason,
+            expires,
+            created: Utc::now(),
+            status: Status::Pending,
+            resolved: false,
+        });
+        self.next_id
+    }
+
+    /// Records a decision on a pending proposal.
+    pub fn decide(&mut self, id: u64, status: Status, by: String) -> Result<&Proposal, String> {
+        let proposal = match self.proposals.iter_mut().find(|proposal| proposal.id == id) {
+            Some(value) => value,
+            None => return Err(format!("unknown proposal: {}", id)),
+        };
+        if proposal.status != Status::Pending {
+            return Err(format!(
+                "proposal {} has already been {}",
+                id, proposal.status
+            ));
+        }
+
+        proposal.status = status;
+        self.audit.push(Decision {
+            proposal_id: id,
+            status,
+            by,
+            when: Utc::now(),
+        });
+
+        Ok(proposal)
+    }
+
+    /// Expires a proposal which hasn't been applied yet, e.g. once the bans behind it were lifted.
+    pub fn expire(&mut self, id: u64, by: String) -> Result<&Proposal, String> {
+        let proposal = match self.proposals.iter_mut().find(|proposal| proposal.id == id) {
+            Some(value) => value,
+            None => return Err(format!("unknown proposal: {}", id)),
+        };
+        if proposal.resolved {
+            return Err(format!(
+                "proposal {} has already been {}",
+                id, proposal.status
+            ));
+        }
+
+        proposal.status = Status::Expired;
+        self.audit.push(Decision {
+            proposal_id: id,
+            status: Status::Expired,
+            by,
+            when: Utc::now(),
+        });
+
+        Ok(proposal)
+    }
+}
@@ -0,0 +1,65 @@
+use crate::config::{Push, PushService};
+use crate::notifier::Severity;
+use serde_json::json;
+
+pub(crate) async fn push_send(
+    http: &reqwest::Client,
+    push: &Push,
+    title: &str,
+    body: &str,
+    severity: Severity,
+    tags: &[String],
+) -> Result<(), String> {
+    let request = match push.service {
+        PushService::Ntfy => {
+            // See: https://docs.ntfy.sh/publish/
+            let priority = match severity {
+                Severity::Low => 2,
+                Severity::Medium => 3,
+                Severity::High => 4,
+                Severity::Critical => 5,
+            };
+            let request = http
+                .post(&push.url)
+                .header("Title", title)
+                .header("Priority", priority.to_string())
+                .header("Tags", tags.join(","))
+                .header("Markdown", "yes")
+                .body(body.to_string());
+            match &push.token {
+                Some(token) => request.bearer_auth(token),
+                None => request,
+            }
+        }
+        PushService::Gotify => {
+            // See: https://gotify.net/docs/pushmsg
+            let priority = match severity {
+                Severity::Low => 2,
+                Severity::Medium => 5,
+                Severity::High => 8,
+                Severity::Critical => 10,
+            };
+            let body = json!({
+                "title": title,
+                "message": body,
+                "priority": priority,
+                "extras": {
+                    "client::display": { "contentType": "text/markdown" }
+                }
+            });
+            let request = http
+                .post(format!("{}/message", push.url.trim_end_matches('/')))
+                .json(&body);
+            match &push.token {
+                Some(token) => request.header("X-Gotify-Key", token),
+                None => request,
+            }
+        }
+    };
+
+    match request.send().await {
+        Ok(response) if !response.status().is_success() => Err(response.status().to_string()),
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.to_string()),
+    }
+}
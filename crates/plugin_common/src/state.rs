@@ -0,0 +1,116 @@
+use crate::config;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use tracing::error;
+
+const EXTENSION: &str = "json";
+
+/// Persists plugin state as JSON documents, so it survives restarts.
+#[derive(Clone)]
+pub struct State {
+    directory: PathBuf,
+}
+
+impl State {
+    pub fn new(config: &config::State) -> Self {
+        let directory = PathBuf::from(&config.directory);
+        if let Err(err) = fs::create_dir_all(&directory) {
+            error!(
+                "failed to create state directory: {} -> {}",
+                directory.display(),
+                err
+            );
+        }
+
+        State { directory }
+    }
+
+    /// Loads the named document, falling back to its default if missing or invalid. An invalid
+    /// document is moved aside, so it is not overwritten by the next save.
+    pub fn load<T: DeserializeOwned + Default>(&self, name: &str) -> T {
+        let filepath = self.filepath(name);
+        if !filepath.is_file() {
+            return T::default();
+        }
+
+        let contents = match fs::read_to_string(&filepath) {
+            Ok(data) => data,
+            Err(err) => {
+                error!("failed to read state: {} -> {}", filepath.display(), err);
+                return T::default();
+            }
+        };
+        match serde_json::from_str::<T>(&contents) {
+            Ok(value) => value,
+            Err(err) => {
+                error!("failed to parse state: {} -> {}", filepath.display(), err);
+
+                // Keep the document for inspection or manual recovery
+                let corrupt = filepath.with_extension(format!("{}.corrupt", EXTENSION));
+                if let Err(err) = fs::rename(&filepath, &corrupt) {
+                    error!("failed to move state: {} -> {}", filepath.display(), err);
+                }
+                T::default()
+            }
+        }
+    }
+
+    /// Loads and removes the named document, e.g. a request left by another process.
+    pub fn take<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        let filepath = self.filepath(name);
+        if !filepath.is_file() {
+            return None;
+        }
+
+        // Move the document aside first, so a concurrent write is kept for the next take
+        let taken = filepath.with_extension("taken");
+        if let Err(err) = fs::rename(&filepath, &taken) {
+            error!("failed to take state: {} -> {}", filepath.display(), err);
+            return None;
+        }
+        let contents = fs::read_to_string(&taken);
+        if let Err(err) = fs::remove_file(&taken) {
+            error!("failed to remove state: {} -> {}", taken.display(), err);
+        }
+
+        match contents
+            .map_err(|err| err.to_string())
+            .and_then(|data| serde_json::from_str::<T>(&data).map_err(|err| err.to_string()))
+        {
+            Ok(value) => Some(value),
+            Err(err) => {
+                error!("failed to parse state: {} -> {}", filepath.display(), err);
+                None
+            }
+        }
+    }
+
+    pub fn save<T: Serialize>(&self, name: &str, value: &T) {
+        let filepath = self.filepath(name);
+        let contents = match serde_json::to_string_pretty(value) {
+            Ok(data) => data,
+            Err(err) => {
+                error!(
+                    "failed to serialize state: {} -> {}",
+                    filepath.display(),
+                    err
+                );
+                return;
+            }
+        };
+
+        // Write to a temporary file first, so a crash never leaves a partial document
+        let temporary = filepath.with_extension("tmp");
+        if let Err(err) =
+            fs::write(&temporary, contents).and_then(|_| fs::rename(&temporary, &filepath))
+        {
+            error!("failed to write state: {} -> {}", filepath.display(), err);
+        }
+    }
+
+    fn filepath(&self, name: &str) -> PathBuf {
+        self.directory.join(format!("{}.{}", name, EXTENSION))
+    }
+}
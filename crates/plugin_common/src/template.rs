@@ -0,0 +1,287 @@
+use crate::config;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tracing::{debug, error, warn};
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+const EXTENSION: &str = "md";
+
+pub const AUTO_REPLY: &str = "auto_reply";
+pub const AUTO_REPLY_ANSWER: &str = "auto_reply_answer";
+pub const COMMAND_AUDIT: &str = "command_audit";
+pub const COMMAND_CONFIRM: &str = "command_confirm";
+pub const COMMAND_DENIED: &str = "command_denied";
+pub const COMMAND_FAILED: &str = "command_failed";
+pub const COMMAND_HELP: &str = "command_help";
+pub const COMMAND_INVALID: &str = "command_invalid";
+pub const COMMAND_REFUSED: &str = "command_refused";
+pub const COMMAND_RESULT: &str = "command_result";
+pub const COMMAND_USAGE: &str = "command_usage";
+pub const FEDERATED_BAN: &str = "federated_ban";
+pub const FEDERATED_BAN_DECISION: &str = "federated_ban_decision";
+pub const FEDERATED_BAN_PENDING: &str = "federated_ban_pending";
+pub const FEDERATED_BAN_PROPOSAL: &str = "federated_ban_proposal";
+pub const FEDERATED_BAN_REPORT: &str = "federated_ban_report";
+pub const FEDERATED_COMMUNITY_BAN: &str = "federated_community_ban";
+pub const FEDERATED_REMOVAL: &str = "federated_removal";
+pub const LOCAL_MODERATOR_CONFLICT: &str = "local_moderator_conflict";
+pub const LOCAL_MODERATOR_RATE: &str = "local_moderator_rate";
+pub const MESSAGE_FORWARD: &str = "message_forward";
+pub const MESSAGE_QUARANTINE: &str = "message_quarantine";
+pub const MODLOG_COMMENT_REMOVAL: &str = "modlog_comment_removal";
+pub const MODLOG_COMMUNITY_BAN: &str = "modlog_community_ban";
+pub const MODLOG_POST_REMOVAL: &str = "modlog_post_removal";
+pub const MODLOG_SITE_BAN: &str = "modlog_site_ban";
+pub const MODMAIL_MESSAGE: &str = "modmail_message";
+pub const MODMAIL_REPLY: &str = "modmail_reply";
+pub const NOTIFICATION_DIGEST: &str = "notification_digest";
+pub const PUSH_TITLE: &str = "push_title";
+
+/// Built-in templates, used whenever no override exists for the configured locale.
+const DEFAULTS: &[(&str, &str)] = &[
+    (
+        AUTO_REPLY,
+        "Thanks for your message! This account is operated by a bot, \
+        please reach out to the admins of this instance for help.",
+    ),
+    (
+        AUTO_REPLY_ANSWER,
+        "{{ answer }}\r\n\r\n*This is an automated reply.*",
+    ),
+    (
+        COMMAND_AUDIT,
+        "`{{ sender }}` performed a message command:\r\n{{ command }}\r\n\r\nresult:\r\n{{ result }}",
+    ),
+    (
+        COMMAND_CONFIRM,
+        "Reply with `!confirm {{ code }}` before {{ deadline }} to perform:\r\n{{ command }}",
+    ),
+    (
+        COMMAND_DENIED,
+        "`{{ sender }}` was denied a message command:\r\n{{ command }}",
+    ),
+    (COMMAND_FAILED, "command failed: `{{ error }}`"),
+    (
+        COMMAND_HELP,
+        "Available commands:\r\n{{ commands }}\r\n\r\n\
+        Reply with `!help <command>` for details, quote arguments containing spaces",
+    ),
+    (COMMAND_INVALID, "invalid command: {{ error }}"),
+    (
+        COMMAND_REFUSED,
+        "command refused: {{ reason }}\r\n{{ command }}",
+    ),
+    (
+        COMMAND_RESULT,
+        "command performed:\r\n{{ command }}\r\n\r\nresult:\r\n{{ result }}",
+    ),
+    (
+        COMMAND_USAGE,
+        "`{{ usage }}`\r\n\r\n{{ summary }}\r\n\r\nAliases: {{ aliases }}",
+    ),
+    (FEDERATED_BAN, "Federated ban:\r\n{{ action }}"),
+    (
+        FEDERATED_BAN_DECISION,
+        "Federated ban proposal `{{ id }}` for {{ user }} has been {{ status }} by `{{ by }}`",
+    ),
+    (
+        FEDERATED_BAN_PENDING,
+        "Federated ban pending quorum, weight {{ weight }} of {{ threshold }}:\r\n{{ action }}",
+    ),
+    (
+        FEDERATED_BAN_PROPOSAL,
+        "Proposed federated ban `{{ id }}`, reply with `!approve {{ id }}` or `!reject {{ id }}` \
+        before {{ deadline }}:\r\n{{ action }}",
+    ),
+    (
+        FEDERATED_BAN_REPORT,
+        "Federated ban report, {{ count }} local ban(s) with a remote source that has expired \
+        or been lifted:\r\n{{ bans }}",
+    ),
+    (
+        FEDERATED_COMMUNITY_BAN,
+        "Federated community ban to {{ community }}:\r\n{{ action }}",
+    ),
+    (FEDERATED_REMOVAL, "Federated removal:\r\n{{ action }}"),
+    (
+        LOCAL_MODERATOR_CONFLICT,
+        "Local moderator {{ mod }} removed content of fellow moderator {{ user }} \
+        in {{ community }}:\r\n{{ action }}",
+    ),
+    (
+        LOCAL_MODERATOR_RATE,
+        "Local moderator {{ mod }} performed {{ count }} {{ kind }} within {{ window }} seconds, \
+        above the threshold of {{ threshold }}",
+    ),
+    (
+        MESSAGE_FORWARD,
+        "* user = {{ user }}\r\n\
+        * message = `{{ message }}`",
+    ),
+    (
+        MESSAGE_QUARANTINE,
+        "Quarantined message digest, {{ count }} filtered message(s):\r\n\r\n{{ messages }}",
+    ),
+    (
+        MODLOG_COMMENT_REMOVAL,
+        "* comment_removed = `{{ is_removed }}`\r\n\
+        * user = {{ user }}\r\n\
+        * comment = {{ comment }}\r\n\
+        * mod = {{ mod }}\r\n\
+        * reason = `{{ reason }}`",
+    ),
+    (
+        MODLOG_COMMUNITY_BAN,
+        "* community_ban = `{{ is_banned }}`\r\n\
+        * user = {{ user }}\r\n\
+        * community = {{ community }}\r\n\
+        * mod = {{ mod }}\r\n\
+        * reason = `{{ reason }}`\r\n\
+        * expires = `{{ expires }}`",
+    ),
+    (
+        MODLOG_POST_REMOVAL,
+        "* post_removed = `{{ is_removed }}`\r\n\
+        * user = {{ user }}\r\n\
+        * post = {{ post }}\r\n\
+        * mod = {{ mod }}\r\n\
+        * reason = `{{ reason }}`",
+    ),
+    (
+        MODLOG_SITE_BAN,
+        "* site_ban = `{{ is_banned }}`\r\n\
+        * user = {{ user }}\r\n\
+        * mod = {{ mod }}\r\n\
+        * reason = `{{ reason }}`\r\n\
+        * expires = `{{ expires }}`",
+    ),
+    (
+        MODMAIL_MESSAGE,
+        "Modmail ticket `{{ id }}` from {{ user }}, reply with `!reply {{ id }} <message>`:\r\n\
+        {{ message }}",
+    ),
+    (
+        MODMAIL_REPLY,
+        "{{ message }}\r\n\r\n\
+        *Reply to this message to continue the conversation with the admin team.*",
+    ),
+    (
+        NOTIFICATION_DIGEST,
+        "Notification digest, {{ count }} notification(s):\r\n\r\n{{ notifications }}",
+    ),
+    (PUSH_TITLE, "AutoMod: {{ event }}"),
+];
+
+/// A named template along with the values to render it with.
+pub struct Message {
+    name: &'static str,
+    values: HashMap<&'static str, String>,
+}
+
+impl Message {
+    pub fn new(name: &'static str) -> Self {
+        Message {
+            name,
+            values: HashMap::new(),
+        }
+    }
+
+    pub fn with(mut self, key: &'static str, value: impl ToString) -> Self {
+        self.values.insert(key, value.to_string());
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+pub struct Templates {
+    templates: HashMap<String, String>,
+}
+
+impl Templates {
+    pub fn load(config: &config::Templates) -> Self {
+        let mut templates = DEFAULTS
+            .iter()
+            .map(|(name, template)| (name.to_string(), template.to_string()))
+            .collect::<HashMap<String, String>>();
+
+        // Apply overrides from the templates directory
+        // Locale specific templates take precedence over shared ones
+        if let Some(directory) = &config.directory {
+            let directory = Path::new(directory);
+            for dir in [directory.to_path_buf(), directory.join(&config.locale)] {
+                for (name, template) in templates.iter_mut() {
+                    let filepath = dir.join(format!("{}.{}", name, EXTENSION));
+                    if !filepath.is_file() {
+                        continue;
+                    }
+
+                    match fs::read_to_string(&filepath) {
+                        Ok(contents) => {
+                            debug!("Loaded template: {}", filepath.display());
+                            *template = contents.trim_end().to_string();
+                        }
+                        Err(err) => {
+                            error!("failed to read template: {} -> {}", filepath.display(), err)
+                        }
+                    }
+                }
+            }
+        }
+
+        // Apply inline overrides from the configuration
+        for (name, template) in &config.messages {
+            match templates.get_mut(name) {
+                Some(value) => *value = template.clone(),
+                None => warn!("unknown template: {}", name),
+            }
+        }
+
+        Templates { templates }
+    }
+
+    pub fn render(&self, message: &Message) -> String {
+        let template = match self.templates.get(message.name) {
+            Some(value) => value.as_str(),
+            None => {
+                error!("missing template: {}", message.name);
+                return String::new();
+            }
+        };
+
+        let mut output = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find(OPEN) {
+            output.push_str(&rest[..start]);
+            let remaining = &rest[start + OPEN.len()..];
+            let end = match remaining.find(CLOSE) {
+                Some(value) => value,
+                None => {
+                    // Treat an unterminated placeholder as plain text
+                    rest = &rest[start..];
+                    break;
+                }
+            };
+
+            let key = remaining[..end].trim();
+            match message.values.get(key) {
+                Some(value) => output.push_str(value),
+                None => warn!("template '{}' has unknown value: {}", message.name, key),
+            }
+            rest = &remaining[end + CLOSE.len()..];
+        }
+        output.push_str(rest);
+
+        output
+    }
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        Templates::load(&config::Templates::default())
+    }
+}
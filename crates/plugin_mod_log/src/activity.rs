@@ -0,0 +1,75 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+pub const STATE: &str = "moderator_activity";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    Ban,
+    Removal,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Kind::Ban => "bans",
+            Kind::Removal => "removals",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    moderator: i32,
+    kind: Kind,
+    when: DateTime<Utc>,
+}
+
+/// Recent actions of local moderators, used to alert on unusual rates of activity.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Activity {
+    actions: Vec<Entry>,
+    alerts: Vec<Entry>,
+}
+
+impl Activity {
+    /// Records an action, returning the number of actions of the same kind by the moderator.
+    pub fn record(&mut self, moderator: i32, kind: Kind, now: DateTime<Utc>) -> usize {
+        self.actions.push(Entry {
+            moderator,
+            kind,
+            when: now,
+        });
+        self.actions
+            .iter()
+            .filter(|entry| entry.moderator == moderator && entry.kind == kind)
+            .count()
+    }
+
+    /// Marks a moderator as alerted, returning false if they already have been.
+    pub fn alert(&mut self, moderator: i32, kind: Kind, now: DateTime<Utc>) -> bool {
+        if self
+            .alerts
+            .iter()
+            .any(|entry| entry.moderator == moderator && entry.kind == kind)
+        {
+            return false;
+        }
+
+        self.alerts.push(Entry {
+            moderator,
+            kind,
+            when: now,
+        });
+        true
+    }
+
+    /// Drops any actions and alerts older than the window.
+    pub fn prune(&mut self, window: Duration, now: DateTime<Utc>) {
+        self.actions.retain(|entry| now - entry.when < window);
+        self.alerts.retain(|entry| now - entry.when < window);
+    }
+}
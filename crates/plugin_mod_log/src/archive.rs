@@ -0,0 +1,292 @@
+use crate::config;
+use crate::config::ArchiveFormat;
+use chrono::{DateTime, Utc};
+use lemmy_client::model::{ModlogEntry, Person};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+pub const STATE: &str = "modlog_archive";
+
+/// A flattened modlog entry, as stored in the archive.
+#[derive(Serialize, Deserialize)]
+pub struct Record {
+    pub id: i32,
+    pub kind: String,
+    pub when: DateTime<Utc>,
+    pub moderator: Option<String>,
+    pub instance: Option<String>,
+    pub user: Option<String>,
+    pub community: Option<String>,
+    pub target: Option<String>,
+    pub reason: Option<String>,
+    pub is_active: bool,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl From<&ModlogEntry> for Record {
+    fn from(value: &ModlogEntry) -> Self {
+        let name = |person: &Person| format!("{}@{}", person.name, person.instance);
+        Record {
+            id: value.id,
+            kind: value.kind.to_string(),
+            when: value.when,
+            moderator: value.moderator.as_ref().map(name),
+            instance: value
+                .moderator
+                .as_ref()
+                .map(|moderator| moderator.instance.clone()),
+            user: value.user.as_ref().map(name),
+            community: value
+                .community
+                .as_ref()
+                .map(|community| format!("{}@{}", community.name, community.instance)),
+            target: value.target.clone(),
+            reason: value.reason.clone(),
+            is_active: value.is_active,
+            expires: value.expires,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Filter {
+    pub since: Option<DateTime<Utc>>,
+    pub kind: Option<String>,
+    pub instance: Option<String>,
+}
+
+impl Filter {
+    fn matches(&self, record: &Record) -> bool {
+        self.since.map_or(true, |since| since <= record.when)
+            && self.kind.as_ref().map_or(true, |kind| *kind == record.kind)
+            && self
+                .instance
+                .as_ref()
+                .map_or(true, |instance| record.instance.as_ref() == Some(instance))
+    }
+}
+
+/// Append-only store of every modlog entry seen by the bot.
+pub struct Archive {
+    format: ArchiveFormat,
+    path: PathBuf,
+}
+
+impl Archive {
+    pub fn new(config: &config::ModLog) -> Self {
+        let extension = match config.archive_format {
+            ArchiveFormat::Jsonl => "jsonl",
+            ArchiveFormat::Csv => "csv",
+            ArchiveFormat::Sqlite => "sqlite",
+        };
+        Archive {
+            format: config.archive_format,
+            path: PathBuf::from(&config.archive_path).with_extension(extension),
+        }
+    }
+
+    pub fn append(&self, records: &[Record]) -> Result<(), String> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        match self.format {
+            ArchiveFormat::Jsonl => {
+                let file = self.open()?;
+                write(records, ArchiveFormat::Jsonl, file)
+            }
+            ArchiveFormat::Csv => {
+                // Only write the header when starting a new archive
+                let is_new = self.path.metadata().map_or(true, |meta| meta.len() == 0);
+                let file = self.open()?;
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(is_new)
+                    .from_writer(file);
+                for record in records {
+                    writer.serialize(record).map_err(|err| err.to_string())?;
+                }
+                writer.flush().map_err(|err| err.to_string())
+            }
+            ArchiveFormat::Sqlite => sqlite::append(&self.path, records),
+        }
+    }
+
+    /// Reads all archived entries matching the filter, oldest first.
+    pub fn read(&self, filter: &Filter) -> Result<Vec<Record>, String> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut records = match self.format {
+            ArchiveFormat::Jsonl => {
+                let file = File::open(&self.path).map_err(|err| err.to_string())?;
+                let mut records = vec![];
+                for line in BufReader::new(file).lines() {
+                    let line = line.map_err(|err| err.to_string())?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    records.push(serde_json::from_str(&line).map_err(|err| err.to_string())?);
+                }
+                records
+            }
+            ArchiveFormat::Csv => {
+                let mut reader =
+                    csv::Reader::from_path(&self.path).map_err(|err| err.to_string())?;
+                reader
+                    .deserialize()
+                    .collect::<Result<Vec<Record>, _>>()
+                    .map_err(|err| err.to_string())?
+            }
+            ArchiveFormat::Sqlite => sqlite::read(&self.path)?,
+        };
+
+        records.retain(|record| filter.matches(record));
+        records.sort_by_key(|record| record.when);
+
+        Ok(records)
+    }
+
+    fn open(&self) -> Result<File, String> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|err| format!("{}: {}", self.path.display(), err))
+    }
+}
+
+/// Writes records as JSON lines or CSV, e.g. when exporting the archive.
+pub fn write(records: &[Record], format: ArchiveFormat, writer: impl Write) -> Result<(), String> {
+    match format {
+        ArchiveFormat::Jsonl => {
+            let mut writer = writer;
+            for record in records {
+                let line = serde_json::to_string(record).map_err(|err| err.to_string())?;
+                writeln!(writer, "{}", line).map_err(|err| err.to_string())?;
+            }
+            writer.flush().map_err(|err| err.to_string())
+        }
+        ArchiveFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(writer);
+            for record in records {
+                writer.serialize(record).map_err(|err| err.to_string())?;
+            }
+            writer.flush().map_err(|err| err.to_string())
+        }
+        ArchiveFormat::Sqlite => Err("unable to write SQLite to a stream".to_string()),
+    }
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::Record;
+    use chrono::{DateTime, Utc};
+    use rusqlite::types::Type;
+    use rusqlite::{params, Connection};
+    use std::path::Path;
+
+    const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS modlog (
+        id INTEGER NOT NULL,
+        kind TEXT NOT NULL,
+        time TEXT NOT NULL,
+        moderator TEXT,
+        instance TEXT,
+        user TEXT,
+        community TEXT,
+        target TEXT,
+        reason TEXT,
+        is_active INTEGER NOT NULL,
+        expires TEXT,
+        PRIMARY KEY (kind, id)
+    )";
+
+    fn open(path: &Path) -> Result<Connection, String> {
+        let connection = Connection::open(path).map_err(|err| err.to_string())?;
+        connection
+            .execute(SCHEMA, [])
+            .map_err(|err| err.to_string())?;
+        Ok(connection)
+    }
+
+    fn parse(column: usize, value: String) -> rusqlite::Result<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&value)
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|err| {
+                rusqlite::Error::FromSqlConversionFailure(column, Type::Text, Box::new(err))
+            })
+    }
+
+    pub(super) fn append(path: &Path, records: &[Record]) -> Result<(), String> {
+        let mut connection = open(path)?;
+        let transaction = connection.transaction().map_err(|err| err.to_string())?;
+        for record in records {
+            transaction
+                .execute(
+                    "INSERT OR IGNORE INTO modlog VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                    params![
+                        record.id,
+                        record.kind,
+                        record.when.to_rfc3339(),
+                        record.moderator,
+                        record.instance,
+                        record.user,
+                        record.community,
+                        record.target,
+                        record.reason,
+                        record.is_active,
+                        record.expires.map(|expires| expires.to_rfc3339()),
+                    ],
+                )
+                .map_err(|err| err.to_string())?;
+        }
+        transaction.commit().map_err(|err| err.to_string())
+    }
+
+    pub(super) fn read(path: &Path) -> Result<Vec<Record>, String> {
+        let connection = open(path)?;
+        let mut statement = connection
+            .prepare("SELECT * FROM modlog")
+            .map_err(|err| err.to_string())?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok(Record {
+                    id: row.get(0)?,
+                    kind: row.get(1)?,
+                    when: parse(2, row.get(2)?)?,
+                    moderator: row.get(3)?,
+                    instance: row.get(4)?,
+                    user: row.get(5)?,
+                    community: row.get(6)?,
+                    target: row.get(7)?,
+                    reason: row.get(8)?,
+                    is_active: row.get(9)?,
+                    expires: row
+                        .get::<_, Option<String>>(10)?
+                        .map(|value| parse(10, value))
+                        .transpose()?,
+                })
+            })
+            .map_err(|err| err.to_string())?;
+        rows.collect::<Result<Vec<Record>, _>>()
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+mod sqlite {
+    use super::Record;
+    use std::path::Path;
+
+    const UNSUPPORTED: &str = "SQLite archives require building with the `sqlite` feature";
+
+    pub(super) fn append(_path: &Path, _records: &[Record]) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub(super) fn read(_path: &Path) -> Result<Vec<Record>, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+}
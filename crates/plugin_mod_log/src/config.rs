@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Deserialize)]
 #[serde(default)]
@@ -8,7 +9,26 @@ pub struct ModLog {
     pub notify_bans: bool,
     pub notify_federated_bans: bool,
     pub notify_removals: bool,
+    pub notify_federated_removals: bool,
     pub allowlist_federated_actions: Vec<String>,
+    pub federated_ban_weights: HashMap<String, f64>,
+    pub federated_ban_threshold: f64,
+    pub federated_ban_window: i64,
+    pub federated_ban_mode: FederatedBanMode,
+    pub federated_ban_proposal_timeout: i64,
+    pub federated_ban_proposal_auto_apply: bool,
+    pub federated_ban_report_interval: i64,
+    pub allowlist_federated_community_bans: Vec<String>,
+    pub community_ban_same_name: bool,
+    pub community_ban_mapping: HashMap<String, String>,
+    pub allowlist_federated_removals: Vec<String>,
+    pub monitor_local_moderators: bool,
+    pub local_moderator_ban_threshold: usize,
+    pub local_moderator_removal_threshold: usize,
+    pub local_moderator_window: i64,
+    pub archive: bool,
+    pub archive_format: ArchiveFormat,
+    pub archive_path: String,
 }
 
 impl Default for ModLog {
@@ -19,7 +39,41 @@ impl Default for ModLog {
             notify_bans: false,
             notify_federated_bans: false,
             notify_removals: false,
+            notify_federated_removals: false,
             allowlist_federated_actions: vec![],
+            federated_ban_weights: HashMap::new(),
+            federated_ban_threshold: 1.0,
+            federated_ban_window: 604800,
+            federated_ban_mode: FederatedBanMode::Ban,
+            federated_ban_proposal_timeout: 86400,
+            federated_ban_proposal_auto_apply: false,
+            federated_ban_report_interval: 0,
+            allowlist_federated_community_bans: vec![],
+            community_ban_same_name: false,
+            community_ban_mapping: HashMap::new(),
+            allowlist_federated_removals: vec![],
+            monitor_local_moderators: false,
+            local_moderator_ban_threshold: 10,
+            local_moderator_removal_threshold: 25,
+            local_moderator_window: 3600,
+            archive: false,
+            archive_format: ArchiveFormat::Jsonl,
+            archive_path: "modlog".to_string(),
         }
     }
 }
+
+#[derive(Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FederatedBanMode {
+    Ban,
+    Propose,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveFormat {
+    Jsonl,
+    Csv,
+    Sqlite,
+}
@@ -1,36 +1,107 @@
-use chrono::{DateTime, Utc};
-use lemmy_client::model::{ModlogBan, ModlogRemoval, Person};
-use lemmy_client::modlog::modlog_local_get;
+use crate::activity::{Activity, Kind};
+use crate::archive::{Archive, Record};
+use crate::config::FederatedBanMode;
+use crate::message::{ban_message, comment_removal_message, post_removal_message};
+use crate::provenance::{Provenance, Source};
+use crate::quorum::Tally;
+use chrono::{DateTime, Duration, Utc};
+use lemmy_client::comment::comment_remove;
+use lemmy_client::community::{community_ban, community_get, community_moderators_get};
+use lemmy_client::model::{ModlogBan, ModlogCursor, ModlogRemoval, Person};
+use lemmy_client::modlog::{
+    modlog_entries_get, modlog_local_get, modlog_moderators_get, ModlogQuery,
+};
 use lemmy_client::person::person_ban;
+use lemmy_client::post::post_remove;
 use lemmy_client::site::site_admins_get;
 use lemmy_client::Client;
-use plugin_common::notify_admins;
+use plugin_common::notifier::{Event, Notification, Notifier, Severity};
+use plugin_common::proposal;
+use plugin_common::proposal::{Proposals, Status};
+use plugin_common::state::State;
+use plugin_common::template::{
+    Message, FEDERATED_BAN, FEDERATED_BAN_DECISION, FEDERATED_BAN_PENDING, FEDERATED_BAN_PROPOSAL,
+    FEDERATED_BAN_REPORT, FEDERATED_COMMUNITY_BAN, FEDERATED_REMOVAL, LOCAL_MODERATOR_CONFLICT,
+    LOCAL_MODERATOR_RATE,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use tracing::{debug, error};
 
+mod activity;
+pub mod archive;
 pub mod config;
+mod message;
+mod provenance;
+mod quorum;
+
+const ESTABLISHED_ACCOUNT_DAYS: i64 = 30;
+const CURSORS: &str = "modlog_cursors";
+
+/// Bookkeeping of federated site bans.
+struct FederatedBans {
+    tally: Tally,
+    provenance: Provenance,
+}
+
+impl FederatedBans {
+    fn load(state: &State) -> Self {
+        FederatedBans {
+            tally: state.load::<Tally>(quorum::STATE),
+            provenance: state.load::<Provenance>(provenance::STATE),
+        }
+    }
+
+    fn save(&self, state: &State) {
+        state.save(quorum::STATE, &self.tally);
+        state.save(provenance::STATE, &self.provenance);
+    }
+}
+
+/// Highest processed modlog ids, tracked separately for remote and local moderator actions.
+#[derive(Serialize, Deserialize, Default)]
+struct Cursors {
+    remote: ModlogCursor,
+    local: ModlogCursor,
+}
 
 pub struct ModLog {
     config: config::ModLog,
+    state: State,
+    bans: FederatedBans,
+    activity: Activity,
+    cursors: Cursors,
+    archived: ModlogCursor,
     last_run: DateTime<Utc>,
 }
 
 impl ModLog {
-    pub fn new(config: config::ModLog) -> Self {
+    pub fn new(config: config::ModLog, state: State) -> Self {
+        let bans = FederatedBans::load(&state);
+        let activity = state.load::<Activity>(activity::STATE);
+        let cursors = state.load::<Cursors>(CURSORS);
+        let archived = state.load::<ModlogCursor>(archive::STATE);
+
         ModLog {
             config,
+            state,
+            bans,
+            activity,
+            cursors,
+            archived,
             last_run: Utc::now(),
         }
     }
 
-    pub async fn run(&mut self, client: &Client) {
+    pub async fn run(&mut self, client: &Client, notifier: &mut Notifier) {
         // Ensure plugin is enabled
         if !self.config.enabled {
             return;
         }
 
         let now = Utc::now();
-        let since = self.last_run;
-        let elapsed = now - since;
+        let elapsed = now - self.last_run;
 
         // Validate plugin is scheduled to run
         if elapsed.num_seconds() < self.config.interval {
@@ -40,8 +111,28 @@ impl ModLog {
 
         debug!("Checking modlog...");
 
-        // Get all modlog actions against local users since last run
-        match modlog_local_get(client, since).await {
+        if self.config.archive {
+            // Archive every modlog entry seen
+            archive_modlog(client, &self.config, &mut self.archived).await;
+            self.state.save(archive::STATE, &self.archived);
+        }
+
+        if self.config.federated_ban_mode == FederatedBanMode::Propose {
+            // Apply any decisions made on proposed bans
+            resolve_proposals(client, notifier, &self.config, &self.state, &mut self.bans).await;
+            self.bans.save(&self.state);
+        }
+
+        if self.config.federated_ban_report_interval > 0 {
+            // Report local bans which have outlived their remote sources
+            report_federated_bans(notifier, &self.config, &mut self.bans.provenance);
+            self.bans.save(&self.state);
+        }
+
+        // Get all unprocessed modlog actions against local users
+        match modlog_local_get(client, &self.cursors.remote).await {
+            // Establish the cursor on the first run, rather than acting on old entries
+            Ok(actions) if self.cursors.remote.is_empty() => self.cursors.remote = actions.cursor,
             Ok(actions) => {
                 // Get list of local admins
                 let admins = match site_admins_get(client).await {
@@ -53,63 +144,606 @@ impl ModLog {
                 };
 
                 let notify_bans = self.config.notify_bans;
-                let notify_federated_bans = self.config.notify_federated_bans;
-                let allowlist = &self.config.allowlist_federated_actions;
+                let federate_bans = !self.config.allowlist_federated_actions.is_empty()
+                    || !self.config.federated_ban_weights.is_empty();
+                let community_allowlist = &self.config.allowlist_federated_community_bans;
 
-                if notify_bans || !allowlist.is_empty() {
+                if notify_bans || federate_bans || !community_allowlist.is_empty() {
                     for action in actions.bans {
                         if notify_bans {
                             // Notify admins on any ban actions
-                            notify_admins(client, &admins, action.to_string()).await;
+                            let mut notification =
+                                Notification::new(Event::ModlogBan, ban_message(&action))
+                                    .user(action.user())
+                                    .instance(action.moderator().instance.clone());
+                            if let ModlogBan::Community { community, .. } = &action {
+                                notification = notification.community(format!(
+                                    "{}@{}",
+                                    community.name, community.instance
+                                ));
+                            }
+                            notifier.notify(notification);
                         }
 
-                        if !allowlist.is_empty() {
-                            // Federate bans from allowed instances
-                            federate_ban_action(
+                        match action {
+                            ModlogBan::Site { .. } if federate_bans => {
+                                // Federate bans from trusted instances
+                                federate_ban_action(
+                                    client,
+                                    notifier,
+                                    &admins,
+                                    &self.config,
+                                    &self.state,
+                                    &mut self.bans,
+                                    action,
+                                )
+                                .await;
+                            }
+                            ModlogBan::Community { .. } if !community_allowlist.is_empty() => {
+                                // Federate community bans from allowed instances
+                                federate_community_ban_action(
+                                    client,
+                                    notifier,
+                                    &admins,
+                                    &self.config,
+                                    action,
+                                )
+                                .await;
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    self.bans.save(&self.state);
+                }
+
+                let notify_removals = self.config.notify_removals;
+                let removal_allowlist = &self.config.allowlist_federated_removals;
+
+                if notify_removals || !removal_allowlist.is_empty() {
+                    for action in actions.removals {
+                        if !removal_allowlist.is_empty() {
+                            // Federate removals from allowed instances
+                            federate_removal_action(
                                 client,
+                                notifier,
                                 &admins,
-                                allowlist,
-                                action,
-                                notify_federated_bans,
+                                &self.config,
+                                &action,
                             )
                             .await;
                         }
-                    }
-                }
 
-                if self.config.notify_removals {
-                    // Notify admins on any removal actions
-                    for action in actions.removals {
-                        match action {
-                            ModlogRemoval::Comment(comment) => {
-                                notify_admins(client, &admins, comment.to_string()).await;
-                            }
+                        if !notify_removals {
+                            continue;
+                        }
+
+                        // Notify admins on any removal actions
+                        let (message, user, moderator) = match &action {
+                            ModlogRemoval::Comment(comment) => (
+                                comment_removal_message(comment),
+                                &comment.user,
+                                &comment.moderator,
+                            ),
                             ModlogRemoval::Post(post) => {
-                                notify_admins(client, &admins, post.to_string()).await;
+                                (post_removal_message(post), &post.user, &post.moderator)
                             }
-                        }
+                        };
+                        let notification = Notification::new(Event::ModlogRemoval, message)
+                            .user(user)
+                            .instance(moderator.instance.clone());
+                        notifier.notify(notification);
                     }
                 }
+
+                self.cursors.remote = actions.cursor;
             }
             Err(err) => println!("{}", err),
         }
 
+        if self.config.monitor_local_moderators {
+            // Oversee the actions of local moderators
+            monitor_local_moderators(
+                client,
+                notifier,
+                &self.config,
+                &mut self.activity,
+                &mut self.cursors.local,
+            )
+            .await;
+            self.state.save(activity::STATE, &self.activity);
+        }
+
+        self.state.save(CURSORS, &self.cursors);
+
         debug!("Finished checking modlog!");
     }
 }
 
+async fn archive_modlog(client: &Client, config: &config::ModLog, cursor: &mut ModlogCursor) {
+    let entries = match modlog_entries_get(client, &ModlogQuery::new()).await {
+        Ok(entries) => entries,
+        Err(err) => {
+            error!("{}", err);
+            return;
+        }
+    };
+
+    // Only advance the cursor once the entries have been stored
+    let records = entries
+        .iter()
+        .filter(|entry| cursor.is_new(entry.kind, entry.id))
+        .map(Record::from)
+        .collect::<Vec<Record>>();
+    match Archive::new(config).append(&records) {
+        Ok(_) => {
+            for entry in &entries {
+                cursor.advance(entry.kind, entry.id);
+            }
+        }
+        Err(err) => error!("failed to archive modlog: {}", err),
+    }
+}
+
+async fn monitor_local_moderators(
+    client: &Client,
+    notifier: &mut Notifier,
+    config: &config::ModLog,
+    activity: &mut Activity,
+    cursor: &mut ModlogCursor,
+) {
+    let now = Utc::now();
+    activity.prune(Duration::seconds(config.local_moderator_window), now);
+
+    // Get all unprocessed bans and removals performed by local moderators
+    let actions = match modlog_moderators_get(client, cursor).await {
+        Ok(actions) => actions,
+        Err(err) => {
+            error!("{}", err);
+            return;
+        }
+    };
+
+    // Establish the cursor on the first run, rather than acting on old entries
+    if cursor.is_empty() {
+        *cursor = actions.cursor;
+        return;
+    }
+
+    // Skip actions performed by the bot itself, e.g. federated bans or message commands
+    let bans = actions
+        .bans
+        .iter()
+        .filter(|action| action.is_banned() && action.moderator().id != client.user_id());
+    for action in bans {
+        let threshold = config.local_moderator_ban_threshold;
+        let count = activity.record(action.moderator().id, Kind::Ban, now);
+        if count > threshold && activity.alert(action.moderator().id, Kind::Ban, now) {
+            notify_moderator_rate(notifier, config, action.moderator(), Kind::Ban, count);
+        }
+    }
+
+    let mut moderators: HashMap<i32, Vec<Person>> = HashMap::new();
+    for action in actions.removals {
+        let (message, moderator, user, community, is_removed) = match &action {
+            ModlogRemoval::Comment(comment) => (
+                comment_removal_message(comment),
+                &comment.moderator,
+                &comment.user,
+                &comment.community,
+                comment.is_removed,
+            ),
+            ModlogRemoval::Post(post) => (
+                post_removal_message(post),
+                &post.moderator,
+                &post.user,
+                &post.community,
+                post.is_removed,
+            ),
+        };
+        if !is_removed || moderator.id == client.user_id() {
+            continue;
+        }
+
+        let count = activity.record(moderator.id, Kind::Removal, now);
+        if count > config.local_moderator_removal_threshold
+            && activity.alert(moderator.id, Kind::Removal, now)
+        {
+            notify_moderator_rate(notifier, config, moderator, Kind::Removal, count);
+        }
+
+        // Ignore moderators removing their own content
+        if user.id == moderator.id {
+            continue;
+        }
+
+        // Look up the moderators of the community, once per run
+        let community_moderators = match moderators.entry(community.id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let name = if community.is_local {
+                    community.name.clone()
+                } else {
+                    format!("{}@{}", community.name, community.instance)
+                };
+                match community_moderators_get(client, name).await {
+                    Ok(list) => entry.insert(list),
+                    Err(err) => {
+                        error!("{}", err);
+                        continue;
+                    }
+                }
+            }
+        };
+
+        // Alert when a moderator removes content of a fellow moderator
+        if community_moderators
+            .iter()
+            .any(|mod_user| mod_user.id == user.id)
+        {
+            let message = Message::new(LOCAL_MODERATOR_CONFLICT)
+                .with("mod", moderator)
+                .with("user", user)
+                .with("community", community)
+                .with("action", notifier.render(&message));
+            let notification =
+                Notification::new(Event::LocalModeratorConflict, message).user(moderator);
+            notifier.notify(notification);
+        }
+    }
+
+    *cursor = actions.cursor;
+}
+
+fn notify_moderator_rate(
+    notifier: &mut Notifier,
+    config: &config::ModLog,
+    moderator: &Person,
+    kind: Kind,
+    count: usize,
+) {
+    let threshold = match kind {
+        Kind::Ban => config.local_moderator_ban_threshold,
+        Kind::Removal => config.local_moderator_removal_threshold,
+    };
+    let message = Message::new(LOCAL_MODERATOR_RATE)
+        .with("mod", moderator)
+        .with("count", count)
+        .with("kind", kind)
+        .with("window", config.local_moderator_window)
+        .with("threshold", threshold);
+    let notification = Notification::new(Event::LocalModeratorRate, message).user(moderator);
+    notifier.notify(notification);
+}
+
 async fn federate_ban_action(
     client: &Client,
-    admins: &Vec<Person>,
-    allowlist: &[String],
+    notifier: &mut Notifier,
+    admins: &[Person],
+    config: &config::ModLog,
+    state: &State,
+    bans: &mut FederatedBans,
     ban: ModlogBan,
-    notify: bool,
 ) {
-    let action = ban.to_string();
+    let action = notifier.render(&ban_message(&ban));
     if let ModlogBan::Site {
         moderator,
         user,
         is_banned,
+        reason,
+        expires,
+    } = ban
+    {
+        // Only act on local, non-admin users
+        if !user.is_local
+            || admins.iter().any(|admin| admin.id == user.id)
+            || user.id == client.user_id()
+        {
+            return;
+        }
+
+        // Verify that the moderator's instance has been trusted
+        let weight = match config.federated_ban_weights.get(&moderator.instance) {
+            Some(weight) => *weight,
+            None if config
+                .allowlist_federated_actions
+                .contains(&moderator.instance) =>
+            {
+                1.0
+            }
+            None => return,
+        };
+
+        // Tally the remote action against any others within the window
+        let now = Utc::now();
+        match is_banned {
+            true => bans
+                .tally
+                .vote(user.id, &moderator.instance, weight, expires, now),
+            false => bans.tally.retract(user.id, &moderator.instance),
+        }
+        bans.tally
+            .prune(Duration::seconds(config.federated_ban_window), now);
+
+        // Record the remote action, tying any local ban to its source
+        let source = Source {
+            instance: moderator.instance.clone(),
+            moderator: moderator.to_string(),
+            reason,
+            expires,
+            lifted: (!is_banned).then_some(now),
+            when: now,
+        };
+        bans.provenance.observe(user.id, user.to_string(), source);
+        bans.provenance
+            .prune(Duration::seconds(config.federated_ban_window), now);
+
+        let total = bans.tally.weight(user.id, now);
+        let has_quorum = total >= config.federated_ban_threshold;
+
+        // Withdraw a pending proposal once a retraction has lost its quorum
+        if !is_banned && !has_quorum {
+            if let Some(id) = bans.tally.proposal(user.id) {
+                let mut proposals = state.load::<Proposals>(proposal::STATE);
+                if let Err(err) = proposals.expire(id, moderator.instance.clone()) {
+                    error!("{}", err);
+                }
+                state.save(proposal::STATE, &proposals);
+            }
+        }
+
+        if has_quorum == bans.tally.is_banned(user.id) {
+            if is_banned && !has_quorum && config.notify_federated_bans {
+                // Notify admins of a ban awaiting quorum
+                let message = Message::new(FEDERATED_BAN_PENDING)
+                    .with("weight", total)
+                    .with("threshold", config.federated_ban_threshold)
+                    .with("action", action);
+                let notification = Notification::new(Event::FederatedBanPending, message)
+                    .user(&user)
+                    .instance(moderator.instance);
+                notifier.notify(notification);
+            }
+            return;
+        }
+
+        // Only lift local bans on an explicit retraction, never once earlier bans have aged out
+        if !has_quorum && is_banned {
+            return;
+        }
+
+        // Only lift local bans once an originating instance has lifted theirs, and every other
+        // source has lifted or expired, as earlier bans may have aged out of the tally
+        if !has_quorum
+            && (!bans.provenance.is_source(user.id, &moderator.instance)
+                || bans.provenance.has_active_source(user.id, now))
+        {
+            return;
+        }
+
+        // Propose the ban to admins rather than performing it
+        if has_quorum && config.federated_ban_mode == FederatedBanMode::Propose {
+            let timeout = Duration::seconds(config.federated_ban_proposal_timeout);
+            if bans.tally.proposal(user.id).is_some() {
+                return;
+            }
+
+            let mut proposals = state.load::<Proposals>(proposal::STATE);
+            let reason = format!(
+                "Federated ban from {}",
+                bans.tally.instances(user.id).join(", ")
+            );
+            let id = proposals.propose(
+                user.id,
+                user.to_string(),
+                reason,
+                bans.tally.expires(user.id),
+            );
+            state.save(proposal::STATE, &proposals);
+            bans.tally.set_proposal(user.id, Some(id));
+
+            let message = Message::new(FEDERATED_BAN_PROPOSAL)
+                .with("id", id)
+                .with("deadline", (now + timeout).to_rfc3339())
+                .with("action", action);
+            let notification = Notification::new(Event::FederatedBanProposal, message)
+                .user(&user)
+                .instance(moderator.instance);
+            notifier.notify(notification);
+            return;
+        }
+
+        // Perform ban locally, or lift it once quorum has been lost
+        let (reason, expires) = match has_quorum {
+            true => (
+                format!(
+                    "Federated ban from {}",
+                    bans.tally.instances(user.id).join(", ")
+                ),
+                bans.tally.expires(user.id),
+            ),
+            false => (format!("Federated unban from {}", moderator.instance), None),
+        };
+        if let Err(err) = person_ban(client, user.id, has_quorum, None, Some(reason), expires).await
+        {
+            error!("{}", err);
+            return;
+        }
+        bans.tally.set_banned(user.id, has_quorum, expires);
+        bans.provenance
+            .set_banned(user.id, has_quorum, expires, now);
+
+        if config.notify_federated_bans {
+            // Notify admins of federated action
+            let message = Message::new(FEDERATED_BAN).with("action", action);
+            let mut notification = Notification::new(Event::FederatedBan, message)
+                .user(&user)
+                .instance(moderator.instance);
+            if Utc::now() - user.created >= Duration::days(ESTABLISHED_ACCOUNT_DAYS) {
+                // Escalate bans of established accounts
+                notification = notification.severity(Severity::Critical);
+            }
+            notifier.notify(notification);
+        }
+    }
+}
+
+async fn resolve_proposals(
+    client: &Client,
+    notifier: &mut Notifier,
+    config: &config::ModLog,
+    state: &State,
+    bans: &mut FederatedBans,
+) {
+    let mut proposals = state.load::<Proposals>(proposal::STATE);
+    let now = Utc::now();
+
+    // Decide any proposals which have timed out
+    let timeout = Duration::seconds(config.federated_ban_proposal_timeout);
+    let status = match config.federated_ban_proposal_auto_apply {
+        true => Status::Approved,
+        false => Status::Expired,
+    };
+    let expired = proposals
+        .proposals
+        .iter()
+        .filter(|proposal| proposal.status == Status::Pending && now - proposal.created >= timeout)
+        .map(|proposal| proposal.id)
+        .collect::<Vec<u64>>();
+    for id in expired {
+        if let Err(err) = proposals.decide(id, status, "timeout".to_string()) {
+            error!("{}", err);
+        }
+    }
+
+    // Expire any proposals which haven't been applied, but have since lost their quorum
+    let lost = proposals
+        .proposals
+        .iter()
+        .filter(|proposal| {
+            !proposal.resolved && matches!(proposal.status, Status::Pending | Status::Approved)
+        })
+        .filter(|proposal| {
+            bans.tally.weight(proposal.user_id, now) < config.federated_ban_threshold
+        })
+        .map(|proposal| proposal.id)
+        .collect::<Vec<u64>>();
+    for id in lost {
+        if let Err(err) = proposals.expire(id, "quorum lost".to_string()) {
+            error!("{}", err);
+        }
+    }
+
+    // Apply any decisions which haven't been resolved yet
+    for proposal in proposals.proposals.iter_mut() {
+        if proposal.resolved || proposal.status == Status::Pending {
+            continue;
+        }
+
+        if proposal.status == Status::Approved {
+            if let Err(err) = person_ban(
+                client,
+                proposal.user_id,
+                true,
+                None,
+                Some(proposal.reason.clone()),
+                proposal.expires,
+            )
+            .await
+            {
+                // Try again on the next run
+                error!("{}", err);
+                continue;
+            }
+            bans.tally
+                .set_banned(proposal.user_id, true, proposal.expires);
+            bans.provenance
+                .set_banned(proposal.user_id, true, proposal.expires, now);
+        } else {
+            // Require a fresh quorum before proposing again
+            bans.tally.clear(proposal.user_id);
+        }
+        bans.tally.set_proposal(proposal.user_id, None);
+        proposal.resolved = true;
+
+        if config.notify_federated_bans {
+            // Notify admins of the decision
+            let by = proposals
+                .audit
+                .iter()
+                .rev()
+                .find(|decision| decision.proposal_id == proposal.id)
+                .map(|decision| decision.by.clone())
+                .unwrap_or_default();
+            let message = Message::new(FEDERATED_BAN_DECISION)
+                .with("id", proposal.id)
+                .with("user", &proposal.user)
+                .with("status", proposal.status)
+                .with("by", by);
+            notifier.notify(Notification::new(Event::FederatedBanDecision, message));
+        }
+    }
+
+    state.save(proposal::STATE, &proposals);
+}
+
+fn report_federated_bans(
+    notifier: &mut Notifier,
+    config: &config::ModLog,
+    provenance: &mut Provenance,
+) {
+    let now = Utc::now();
+    let interval = Duration::seconds(config.federated_ban_report_interval);
+
+    // Validate report is scheduled
+    if provenance
+        .last_report
+        .is_some_and(|last_report| now - last_report < interval)
+    {
+        return;
+    }
+
+    let stale = provenance
+        .stale(now)
+        .iter()
+        .map(|origin| {
+            let sources = origin
+                .sources
+                .iter()
+                .map(|source| source.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            let banned = origin
+                .banned
+                .map(|banned| banned.to_rfc3339())
+                .unwrap_or_default();
+            format!("* {}, banned on {}: {}", origin.user, banned, sources)
+        })
+        .collect::<Vec<String>>();
+    provenance.last_report = Some(now);
+    if stale.is_empty() {
+        return;
+    }
+
+    let message = Message::new(FEDERATED_BAN_REPORT)
+        .with("count", stale.len())
+        .with("bans", stale.join("\r\n"));
+    notifier.notify(Notification::new(Event::FederatedBanReport, message));
+}
+
+async fn federate_community_ban_action(
+    client: &Client,
+    notifier: &mut Notifier,
+    admins: &[Person],
+    config: &config::ModLog,
+    ban: ModlogBan,
+) {
+    let action = notifier.render(&ban_message(&ban));
+    if let ModlogBan::Community {
+        moderator,
+        user,
+        community,
+        is_banned,
         expires,
         ..
     } = ban
@@ -123,21 +757,126 @@ async fn federate_ban_action(
         }
 
         // Verify that the moderator's instance has been allowed
-        if allowlist.contains(&moderator.instance) {
-            // Perform ban locally
-            let reason = format!("Federated ban from {}", moderator.instance);
-            if let Err(err) =
-                person_ban(client, user.id, is_banned, None, Some(reason), expires).await
-            {
+        if !config
+            .allowlist_federated_community_bans
+            .contains(&moderator.instance)
+        {
+            return;
+        }
+
+        // Map the remote community onto a local one
+        let remote_name = format!("{}@{}", community.name, community.instance);
+        let local_name = match config.community_ban_mapping.get(&remote_name) {
+            Some(name) => name.clone(),
+            None if config.community_ban_same_name => community.name.clone(),
+            None => {
+                debug!("No local community mapped for: {}", remote_name);
+                return;
+            }
+        };
+        let local_community = match community_get(client, local_name).await {
+            Ok(value) => value,
+            Err(err) => {
                 error!("{}", err);
                 return;
             }
+        };
+        if !local_community.is_local {
+            return;
+        }
 
-            if notify {
-                // Notify admins of federated action
-                let message = format!("Federated ban:\r\n{}", action);
-                notify_admins(client, admins, message).await;
-            }
+        // Perform community ban locally
+        let reason = format!("Federated community ban from {}", remote_name);
+        if let Err(err) = community_ban(
+            client,
+            local_community.id,
+            user.id,
+            is_banned,
+            None,
+            Some(reason),
+            expires,
+        )
+        .await
+        {
+            error!("{}", err);
+            return;
+        }
+
+        if config.notify_federated_bans {
+            // Notify admins of federated action
+            let message = Message::new(FEDERATED_COMMUNITY_BAN)
+                .with("community", &local_community)
+                .with("action", action);
+            let notification = Notification::new(Event::FederatedBan, message)
+                .user(&user)
+                .instance(moderator.instance)
+                .community(local_community.name);
+            notifier.notify(notification);
         }
     }
 }
+
+async fn federate_removal_action(
+    client: &Client,
+    notifier: &mut Notifier,
+    admins: &[Person],
+    config: &config::ModLog,
+    removal: &ModlogRemoval,
+) {
+    let (moderator, user, is_removed, reason) = match removal {
+        ModlogRemoval::Comment(comment) => (
+            &comment.moderator,
+            &comment.user,
+            comment.is_removed,
+            &comment.reason,
+        ),
+        ModlogRemoval::Post(post) => (&post.moderator, &post.user, post.is_removed, &post.reason),
+    };
+
+    // Only act on local, non-admin users
+    if !user.is_local
+        || admins.iter().any(|admin| admin.id == user.id)
+        || user.id == client.user_id()
+    {
+        return;
+    }
+
+    // Verify that the moderator's instance has been allowed
+    if !config
+        .allowlist_federated_removals
+        .contains(&moderator.instance)
+    {
+        return;
+    }
+
+    // Perform removal locally, citing the remote action
+    let reason = format!(
+        "Federated removal by {}@{}: {}",
+        moderator.name,
+        moderator.instance,
+        reason.clone().unwrap_or_default()
+    );
+    let (result, action) = match removal {
+        ModlogRemoval::Comment(comment) => (
+            comment_remove(client, comment.comment.id, is_removed, Some(reason)).await,
+            comment_removal_message(comment),
+        ),
+        ModlogRemoval::Post(post) => (
+            post_remove(client, post.post.id, is_removed, Some(reason)).await,
+            post_removal_message(post),
+        ),
+    };
+    if let Err(err) = result {
+        error!("{}", err);
+        return;
+    }
+
+    if config.notify_federated_removals {
+        // Notify admins of federated action
+        let message = Message::new(FEDERATED_REMOVAL).with("action", notifier.render(&action));
+        let notification = Notification::new(Event::FederatedRemoval, message)
+            .user(user)
+            .instance(moderator.instance.clone());
+        notifier.notify(notification);
+    }
+}
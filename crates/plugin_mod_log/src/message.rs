@@ -0,0 +1,63 @@
+use lemmy_client::model::{ModlogBan, ModlogCommentRemoval, ModlogPostRemoval};
+use plugin_common::template::{
+    Message, MODLOG_COMMENT_REMOVAL, MODLOG_COMMUNITY_BAN, MODLOG_POST_REMOVAL, MODLOG_SITE_BAN,
+};
+
+pub fn ban_message(ban: &ModlogBan) -> Message {
+    match ban {
+        ModlogBan::Site {
+            moderator,
+            user,
+            is_banned,
+            reason,
+            expires,
+        } => Message::new(MODLOG_SITE_BAN)
+            .with("is_banned", is_banned)
+            .with("user", user)
+            .with("mod", moderator)
+            .with("reason", reason.clone().unwrap_or_default())
+            .with(
+                "expires",
+                expires
+                    .map(|exp| exp.to_rfc3339())
+                    .unwrap_or("N/A".to_string()),
+            ),
+        ModlogBan::Community {
+            moderator,
+            user,
+            community,
+            is_banned,
+            reason,
+            expires,
+        } => Message::new(MODLOG_COMMUNITY_BAN)
+            .with("is_banned", is_banned)
+            .with("user", user)
+            .with("community", community)
+            .with("mod", moderator)
+            .with("reason", reason.clone().unwrap_or_default())
+            .with(
+                "expires",
+                expires
+                    .map(|exp| exp.to_rfc3339())
+                    .unwrap_or("N/A".to_string()),
+            ),
+    }
+}
+
+pub fn comment_removal_message(removal: &ModlogCommentRemoval) -> Message {
+    Message::new(MODLOG_COMMENT_REMOVAL)
+        .with("is_removed", removal.is_removed)
+        .with("user", &removal.user)
+        .with("comment", &removal.comment)
+        .with("mod", &removal.moderator)
+        .with("reason", removal.reason.clone().unwrap_or_default())
+}
+
+pub fn post_removal_message(removal: &ModlogPostRemoval) -> Message {
+    Message::new(MODLOG_POST_REMOVAL)
+        .with("is_removed", removal.is_removed)
+        .with("user", &removal.user)
+        .with("post", &removal.post)
+        .with("mod", &removal.moderator)
+        .with("reason", removal.reason.clone().unwrap_or_default())
+}
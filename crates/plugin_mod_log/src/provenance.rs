@@ -0,0 +1,140 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+pub const STATE: &str = "federated_ban_provenance";
+
+/// A remote site ban by a trusted instance.
+#[derive(Serialize, Deserialize)]
+pub struct Source {
+    pub instance: String,
+    pub moderator: String,
+    pub reason: Option<String>,
+    pub expires: Option<DateTime<Utc>>,
+    pub lifted: Option<DateTime<Utc>>,
+    pub when: DateTime<Utc>,
+}
+
+impl Source {
+    /// Whether the remote ban still stands, i.e. it hasn't been lifted or expired.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.lifted.is_none() && self.expires.map_or(true, |expires| now < expires)
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.lifted, self.expires) {
+            (Some(lifted), _) => write!(
+                f,
+                "`{}` lifted their ban on {}",
+                self.instance,
+                lifted.to_rfc3339()
+            ),
+            (None, Some(expires)) => write!(
+                f,
+                "`{}` ban expired on {}",
+                self.instance,
+                expires.to_rfc3339()
+            ),
+            (None, None) => write!(f, "`{}` ban is permanent", self.instance),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Origin {
+    pub user: String,
+    pub sources: Vec<Source>,
+    pub banned: Option<DateTime<Utc>>,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+/// Remote sources of federated site bans, tying each local ban to the instances behind it.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Provenance {
+    origins: HashMap<i32, Origin>,
+    pub last_report: Option<DateTime<Utc>>,
+}
+
+impl Provenance {
+    /// Records a remote ban or unban, a new ban from the same instance replaces the previous one.
+    pub fn observe(&mut self, user_id: i32, user: String, source: Source) {
+        let origin = self.origins.entry(user_id).or_default();
+        origin.user = user;
+        match source.lifted {
+            None => {
+                origin
+                    .sources
+                    .retain(|previous| previous.instance != source.instance);
+                origin.sources.push(source);
+            }
+            Some(lifted) => {
+                for previous in origin.sources.iter_mut() {
+                    if previous.instance == source.instance {
+                        previous.lifted = Some(lifted);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether an instance has contributed to a user's ban.
+    pub fn is_source(&self, user_id: i32, instance: &str) -> bool {
+        self.origins.get(&user_id).is_some_and(|origin| {
+            origin
+                .sources
+                .iter()
+                .any(|source| source.instance == instance)
+        })
+    }
+
+    /// Whether any remote ban of a user still stands, regardless of the window.
+    pub fn has_active_source(&self, user_id: i32, now: DateTime<Utc>) -> bool {
+        self.origins
+            .get(&user_id)
+            .is_some_and(|origin| origin.sources.iter().any(|source| source.is_active(now)))
+    }
+
+    /// Records a local ban, or its removal, performed on behalf of the sources.
+    pub fn set_banned(
+        &mut self,
+        user_id: i32,
+        is_banned: bool,
+        expires: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) {
+        let origin = self.origins.entry(user_id).or_default();
+        origin.banned = is_banned.then_some(now);
+        origin.expires = expires;
+    }
+
+    /// Local bans where any of the remote sources has since been lifted or expired.
+    pub fn stale(&self, now: DateTime<Utc>) -> Vec<&Origin> {
+        let mut origins = self
+            .origins
+            .values()
+            .filter(|origin| origin.banned.is_some())
+            .filter(|origin| origin.sources.iter().any(|source| !source.is_active(now)))
+            .collect::<Vec<&Origin>>();
+        origins.sort_by_key(|origin| origin.banned);
+        origins
+    }
+
+    /// Drops expired local bans, and any sources of users which haven't been banned locally.
+    pub fn prune(&mut self, window: Duration, now: DateTime<Utc>) {
+        for origin in self.origins.values_mut() {
+            if origin.expires.is_some_and(|expires| expires <= now) {
+                origin.banned = None;
+            }
+            if origin.banned.is_none() {
+                origin
+                    .sources
+                    .retain(|source| source.is_active(now) && now - source.when < window);
+            }
+        }
+        self.origins
+            .retain(|_, origin| origin.banned.is_some() || !origin.sources.is_empty());
+    }
+}
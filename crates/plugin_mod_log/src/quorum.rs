@@ -0,0 +1,174 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const STATE: &str = "federated_ban_quorum";
+
+#[derive(Serialize, Deserialize)]
+struct Vote {
+    instance: String,
+    weight: f64,
+    expires: Option<DateTime<Utc>>,
+    when: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Ballot {
+    votes: Vec<Vote>,
+    is_banned: bool,
+    #[serde(default)]
+    expires: Option<DateTime<Utc>>,
+    proposal: Option<u64>,
+}
+
+/// Remote site bans against local users, tallied by the weight of each instance.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Tally {
+    ballots: HashMap<i32, Ballot>,
+}
+
+impl Tally {
+    /// Records a remote ban, replacing any previous ban from the same instance.
+    pub fn vote(
+        &mut self,
+        user_id: i32,
+        instance: &str,
+        weight: f64,
+        expires: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) {
+        let ballot = self.ballots.entry(user_id).or_default();
+        ballot.votes.retain(|vote| vote.instance != instance);
+        ballot.votes.push(Vote {
+            instance: instance.to_string(),
+            weight,
+            expires,
+            when: now,
+        });
+    }
+
+    /// Removes a remote ban, e.g. once the instance has lifted it.
+    pub fn retract(&mut self, user_id: i32, instance: &str) {
+        if let Some(ballot) = self.ballots.get_mut(&user_id) {
+            ballot.votes.retain(|vote| vote.instance != instance);
+        }
+    }
+
+    /// Combined weight of the remote bans for a user, ignoring any which have expired.
+    pub fn weight(&self, user_id: i32, now: DateTime<Utc>) -> f64 {
+        self.ballots
+            .get(&user_id)
+            .map(|ballot| {
+                ballot
+                    .votes
+                    .iter()
+                    .filter(|vote| vote.expires.map_or(true, |expires| now < expires))
+                    .map(|vote| vote.weight)
+                    .sum()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Instances which have banned a user.
+    pub fn instances(&self, user_id: i32) -> Vec<String> {
+        self.ballots
+            .get(&user_id)
+            .map(|ballot| {
+                ballot
+                    .votes
+                    .iter()
+                    .map(|vote| vote.instance.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Latest expiry across the remote bans for a user, none if any are permanent.
+    pub fn expires(&self, user_id: i32) -> Option<DateTime<Utc>> {
+        let votes = &self.ballots.get(&user_id)?.votes;
+        if votes.iter().any(|vote| vote.expires.is_none()) {
+            return None;
+        }
+        votes.iter().filter_map(|vote| vote.expires).max()
+    }
+
+    pub fn is_banned(&self, user_id: i32) -> bool {
+        self.ballots
+            .get(&user_id)
+            .map(|ballot| ballot.is_banned)
+            .unwrap_or_default()
+    }
+
+    /// Records a local ban, or its removal, along with its expiry.
+    pub fn set_banned(&mut self, user_id: i32, is_banned: bool, expires: Option<DateTime<Utc>>) {
+        let ballot = self.ballots.entry(user_id).or_default();
+        ballot.is_banned = is_banned;
+        ballot.expires = expires.filter(|_| is_banned);
+    }
+
+    /// Pending proposal to ban a user, if any.
+    pub fn proposal(&self, user_id: i32) -> Option<u64> {
+        self.ballots.get(&user_id)?.proposal
+    }
+
+    pub fn set_proposal(&mut self, user_id: i32, proposal: Option<u64>) {
+        self.ballots.entry(user_id).or_default().proposal = proposal;
+    }
+
+    /// Discards the remote bans for a user, so a fresh quorum is required.
+    pub fn clear(&mut self, user_id: i32) {
+        if let Some(ballot) = self.ballots.get_mut(&user_id) {
+            ballot.votes.clear();
+        }
+    }
+
+    /// Drops any remote bans older than the window, keeping users which have been banned locally.
+    ///
+    /// Bans behind a pending proposal are kept, so its quorum can still be verified once decided.
+    /// Expired local bans are forgotten, so the user may be banned again by a fresh quorum.
+    pub fn prune(&mut self, window: Duration, now: DateTime<Utc>) {
+        for ballot in self.ballots.values_mut() {
+            if ballot.expires.is_some_and(|expires| expires <= now) {
+                ballot.is_banned = false;
+                ballot.expires = None;
+            }
+            if ballot.proposal.is_none() {
+                ballot.votes.retain(|vote| now - vote.when < window);
+            }
+        }
+        self.ballots.retain(|_, ballot| {
+            !ballot.votes.is_empty() || ballot.is_banned || ballot.proposal.is_some()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expired_ban_allows_new_quorum() {
+        let window = Duration::days(1);
+        let now = Utc::now();
+        let mut tally = Tally::default();
+
+        // Ban locally by quorum, with a temporary ban
+        tally.vote(1, "a.example", 1.0, None, now);
+        tally.vote(1, "b.example", 1.0, None, now);
+        tally.set_banned(1, true, Some(now + Duration::days(3)));
+        tally.prune(window, now);
+        assert!(tally.is_banned(1));
+
+        // Once the local ban expires, the user is no longer considered banned
+        let later = now + Duration::days(4);
+        tally.prune(window, later);
+        assert!(!tally.is_banned(1));
+
+        // A fresh quorum is then able to ban the user again
+        tally.vote(1, "a.example", 1.0, None, later);
+        tally.vote(1, "c.example", 1.0, None, later);
+        tally.prune(window, later);
+        assert!(tally.weight(1, later) >= 2.0);
+        assert!(!tally.is_banned(1));
+    }
+}
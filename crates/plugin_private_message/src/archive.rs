@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use lemmy_client::model::{PrivateMessage, PrivateMessageId};
+use serde::{Deserialize, Serialize};
+
+pub const STATE: &str = "message_archive";
+
+/// A pruned message, kept in the local state store.
+#[derive(Serialize, Deserialize)]
+pub struct Archived {
+    pub id: PrivateMessageId,
+    pub sender_id: i32,
+    pub recipient_id: i32,
+    pub content: String,
+    pub created: DateTime<Utc>,
+    pub archived: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Archive {
+    pub messages: Vec<Archived>,
+}
+
+impl Archive {
+    /// Records a message, unless it was archived by a previous attempt to prune it.
+    pub fn add(&mut self, message: &PrivateMessage, now: DateTime<Utc>) {
+        if self
+            .messages
+            .iter()
+            .any(|archived| archived.id == message.id)
+        {
+            return;
+        }
+
+        self.messages.push(Archived {
+            id: message.id,
+            sender_id: message.sender_id,
+            recipient_id: message.recipient_id,
+            content: message.content.clone(),
+            created: message.created,
+            archived: now,
+        });
+    }
+}
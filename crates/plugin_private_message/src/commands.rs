@@ -1,89 +1,704 @@
+use crate::permission::Permission;
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, Utc};
 use std::fmt::{Display, Formatter};
 
 const PREFIX: char = '!';
+const QUOTE: char = '"';
+
+const APPROVE: &str = "approve";
+const ASSIGN: &str = "assign";
+const CLOSE: &str = "close";
+const COMMUNITY_BAN: &str = "community_ban";
+const CONFIRM: &str = "confirm";
+const FEATURE: &str = "feature";
+const HELP: &str = "help";
+const LOCK: &str = "lock";
 const PURGE_USER: &str = "purge_user";
+const REJECT: &str = "reject";
+const REMOVE: &str = "remove";
+const REPLY: &str = "reply";
+const RESTORE: &str = "restore";
 const SITE_BAN: &str = "site_ban";
 const SITE_BAN_REMOVE: &str = "site_ban_remove";
+const SNOOZE_INSTANCE: &str = "snooze_instance";
+const SNOOZE_USER: &str = "snooze_user";
+const TICKETS: &str = "tickets";
+const UNBAN: &str = "unban";
+const UNFEATURE: &str = "unfeature";
+const UNLOCK: &str = "unlock";
+
+/// Every supported message command, in the order they are listed by `!help`.
+pub static COMMANDS: &[Spec] = &[
+    Spec {
+        name: APPROVE,
+        aliases: &[],
+        summary: "Approves a proposed federated ban",
+        arguments: &[Argument::required("proposal", Kind::Number)],
+        permission: Permission::Admin,
+        is_destructive: false,
+        build: |args| Commands::Approve(args.number()),
+    },
+    Spec {
+        name: ASSIGN,
+        aliases: &[],
+        summary: "Assigns a modmail ticket, to the sender unless another admin is given",
+        arguments: &[
+            Argument::required("ticket", Kind::Number),
+            Argument::optional("admin", Kind::User),
+        ],
+        permission: Permission::Admin,
+        is_destructive: false,
+        build: |args| Commands::Assign(args.number(), args.optional_text()),
+    },
+    Spec {
+        name: CLOSE,
+        aliases: &[],
+        summary: "Closes a modmail ticket, further messages from the user open a new ticket",
+        arguments: &[Argument::required("ticket", Kind::Number)],
+        permission: Permission::Admin,
+        is_destructive: false,
+        build: |args| Commands::Close(args.number()),
+    },
+    Spec {
+        name: COMMUNITY_BAN,
+        aliases: &[],
+        summary: "Bans a user from a community, moderators may only ban from their own communities",
+        arguments: &[
+            Argument::required("community", Kind::Community),
+            Argument::required("user", Kind::User),
+            Argument::required("reason", Kind::Text),
+        ],
+        permission: Permission::Moderator,
+        is_destructive: false,
+        build: |args| Commands::CommunityBan(args.text(), args.text(), args.text()),
+    },
+    Spec {
+        name: CONFIRM,
+        aliases: &[],
+        summary: "Confirms a destructive command using the code from its reply",
+        arguments: &[Argument::required("code", Kind::Number)],
+        permission: Permission::Moderator,
+        is_destructive: false,
+        build: |args| Commands::Confirm(args.number()),
+    },
+    Spec {
+        name: FEATURE,
+        aliases: &["pin"],
+        summary: "Features a post in its community",
+        arguments: &[Argument::required("post", Kind::Url)],
+        permission: Permission::Moderator,
+        is_destructive: false,
+        build: |args| Commands::Feature(args.text(), true),
+    },
+    Spec {
+        name: HELP,
+        aliases: &["commands"],
+        summary: "Lists the available commands, or describes a single command",
+        arguments: &[Argument::optional("name", Kind::Text)],
+        permission: Permission::Moderator,
+        is_destructive: false,
+        build: |args| Commands::Help(args.optional_text()),
+    },
+    Spec {
+        name: LOCK,
+        aliases: &[],
+        summary: "Locks a post, preventing new comments",
+        arguments: &[Argument::required("post", Kind::Url)],
+        permission: Permission::Moderator,
+        is_destructive: false,
+        build: |args| Commands::Lock(args.text(), true),
+    },
+    Spec {
+        name: PURGE_USER,
+        aliases: &["purge"],
+        summary: "Purges a user and their content, this does not federate",
+        arguments: &[
+            Argument::required("user", Kind::User),
+            Argument::required("reason", Kind::Text),
+        ],
+        permission: Permission::Admin,
+        is_destructive: true,
+        build: |args| Commands::PurgeUser(args.text(), args.text()),
+    },
+    Spec {
+        name: REJECT,
+        aliases: &[],
+        summary: "Rejects a proposed federated ban",
+        arguments: &[Argument::required("proposal", Kind::Number)],
+        permission: Permission::Admin,
+        is_destructive: false,
+        build: |args| Commands::Reject(args.number()),
+    },
+    Spec {
+        name: REMOVE,
+        aliases: &[],
+        summary: "Removes a post or comment",
+        arguments: &[
+            Argument::required("link", Kind::Url),
+            Argument::required("reason", Kind::Text),
+        ],
+        permission: Permission::Moderator,
+        is_destructive: false,
+        build: |args| Commands::Remove(args.text(), args.text()),
+    },
+    Spec {
+        name: REPLY,
+        aliases: &[],
+        summary: "Replies to the user of a modmail ticket on behalf of the admin team",
+        arguments: &[
+            Argument::required("ticket", Kind::Number),
+            Argument::required("message", Kind::Text),
+        ],
+        permission: Permission::Admin,
+        is_destructive: false,
+        build: |args| Commands::Reply(args.number(), args.text()),
+    },
+    Spec {
+        name: RESTORE,
+        aliases: &[],
+        summary: "Restores a removed post or comment",
+        arguments: &[
+            Argument::required("link", Kind::Url),
+            Argument::optional("reason", Kind::Text),
+        ],
+        permission: Permission::Moderator,
+        is_destructive: false,
+        build: |args| Commands::Restore(args.text(), args.optional_text()),
+    },
+    Spec {
+        name: SITE_BAN,
+        aliases: &["ban"],
+        summary: "Bans a user from the site, permanently unless an expiry is given",
+        arguments: &[
+            Argument::required("user", Kind::User),
+            Argument::optional("expires", Kind::Expiry),
+            Argument::required("reason", Kind::Text),
+        ],
+        permission: Permission::Admin,
+        is_destructive: false,
+        build: |args| Commands::SiteBan(args.text(), args.optional_expiry(), args.text()),
+    },
+    Spec {
+        name: SITE_BAN_REMOVE,
+        aliases: &[],
+        summary: "Bans a user from the site and removes their content",
+        arguments: &[
+            Argument::required("user", Kind::User),
+            Argument::optional("expires", Kind::Expiry),
+            Argument::required("reason", Kind::Text),
+        ],
+        permission: Permission::Admin,
+        is_destructive: true,
+        build: |args| Commands::SiteBanRemove(args.text(), args.optional_expiry(), args.text()),
+    },
+    Spec {
+        name: SNOOZE_INSTANCE,
+        aliases: &[],
+        summary: "Suppresses notifications about an instance",
+        arguments: &[
+            Argument::required("instance", Kind::Instance),
+            Argument::required("duration", Kind::Duration),
+        ],
+        permission: Permission::Admin,
+        is_destructive: false,
+        build: |args| Commands::SnoozeInstance(args.text(), args.duration()),
+    },
+    Spec {
+        name: SNOOZE_USER,
+        aliases: &[],
+        summary: "Suppresses notifications about a user",
+        arguments: &[
+            Argument::required("user", Kind::User),
+            Argument::required("duration", Kind::Duration),
+        ],
+        permission: Permission::Admin,
+        is_destructive: false,
+        build: |args| Commands::SnoozeUser(args.text(), args.duration()),
+    },
+    Spec {
+        name: TICKETS,
+        aliases: &["modmail"],
+        summary: "Lists open modmail tickets, or shows the history of a single ticket",
+        arguments: &[Argument::optional("ticket", Kind::Number)],
+        permission: Permission::Admin,
+        is_destructive: false,
+        build: |args| Commands::Tickets(args.optional_number()),
+    },
+    Spec {
+        name: UNBAN,
+        aliases: &["site_unban"],
+        summary: "Removes a site ban from a user",
+        arguments: &[
+            Argument::required("user", Kind::User),
+            Argument::required("reason", Kind::Text),
+        ],
+        permission: Permission::Admin,
+        is_destructive: false,
+        build: |args| Commands::Unban(args.text(), args.text()),
+    },
+    Spec {
+        name: UNFEATURE,
+        aliases: &["unpin"],
+        summary: "Removes a featured post from its community",
+        arguments: &[Argument::required("post", Kind::Url)],
+        permission: Permission::Moderator,
+        is_destructive: false,
+        build: |args| Commands::Feature(args.text(), false),
+    },
+    Spec {
+        name: UNLOCK,
+        aliases: &[],
+        summary: "Unlocks a post",
+        arguments: &[Argument::required("post", Kind::Url)],
+        permission: Permission::Moderator,
+        is_destructive: false,
+        build: |args| Commands::Lock(args.text(), false),
+    },
+];
 
 pub enum Commands {
-    SiteBan(String, String),
-    SiteBanRemove(String, String),
+    Approve(u64),
+    Assign(u64, Option<String>),
+    Close(u64),
+    CommunityBan(String, String, String),
+    Confirm(u64),
+    Feature(String, bool),
+    Help(Option<String>),
+    Lock(String, bool),
+    Reject(u64),
+    Remove(String, String),
+    Reply(u64, String),
+    Restore(String, Option<String>),
+    SiteBan(String, Option<DateTime<Utc>>, String),
+    SiteBanRemove(String, Option<DateTime<Utc>>, String),
     PurgeUser(String, String),
+    SnoozeInstance(String, Duration),
+    SnoozeUser(String, Duration),
+    Tickets(Option<u64>),
+    Unban(String, String),
+}
+
+#[derive(Clone, Copy)]
+pub enum Kind {
+    User,
+    Community,
+    Instance,
+    Number,
+    Duration,
+    Expiry,
+    Url,
+    Text,
 }
 
-impl Commands {
-    pub fn parse(value: &str) -> Option<Self> {
-        if !value.starts_with(PREFIX) {
-            return None;
+impl Kind {
+    fn expected(&self) -> &'static str {
+        match self {
+            Kind::User => "a username, e.g. `user` or `user@lemmy.example`",
+            Kind::Community => "a community, e.g. `community` or `community@lemmy.example`",
+            Kind::Instance => "an instance hostname, e.g. `lemmy.example`",
+            Kind::Number => "a number",
+            Kind::Duration => "a duration, e.g. `30m`, `12h`, `7d` or `2w`",
+            Kind::Expiry => "a duration, e.g. `12h` or `7d`, or a future date, e.g. `2030-01-31`",
+            Kind::Url => "a link, e.g. `https://lemmy.example/post/1`",
+            Kind::Text => "text",
+        }
+    }
+
+    /// Whether the token was likely meant as this kind, despite failing to parse.
+    fn resembles(&self, token: &str) -> bool {
+        match self {
+            Kind::Duration | Kind::Expiry => {
+                token.starts_with(|c: char| c.is_ascii_digit())
+                    && !token.contains(char::is_whitespace)
+            }
+            _ => false,
         }
+    }
 
-        let parts = value.splitn(3, ' ').collect::<Vec<&str>>();
-        match &parts[0][1..] {
-            PURGE_USER => {
-                if parts.len() < 3 {
-                    return None;
+    fn parse(&self, token: &str) -> Option<Value> {
+        match self {
+            Kind::User | Kind::Community => {
+                // Accept the `@user@instance` and `!community@instance` notations
+                let token = token.trim_start_matches(['@', '!']);
+                let (name, instance) = token.split_once('@').unwrap_or((token, "lemmy"));
+                let is_valid = !name.is_empty()
+                    && !instance.contains('@')
+                    && token
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || "_@.-".contains(c));
+                match self {
+                    Kind::Community => is_valid.then(|| Value::Community(token.to_string())),
+                    _ => is_valid.then(|| Value::User(token.to_string())),
                 }
+            }
+            Kind::Instance => {
+                let is_valid = token.contains('.')
+                    && token
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || ".-:".contains(c));
+                is_valid.then(|| Value::Instance(token.to_lowercase()))
+            }
+            Kind::Number => token.parse::<u64>().ok().map(Value::Number),
+            Kind::Duration => parse_duration(token)
+                .filter(|duration| Utc::now().checked_add_signed(*duration).is_some())
+                .map(Value::Duration),
+            Kind::Expiry => parse_expiry(token, Utc::now()).map(Value::Expiry),
+            Kind::Url => {
+                let is_valid = token.starts_with("https://") || token.starts_with("http://");
+                is_valid.then(|| Value::Url(token.to_string()))
+            }
+            Kind::Text => Some(Value::Text(token.to_string())),
+        }
+    }
+}
 
-                let username = parts[1].to_string();
-                let reason = parts[2].to_string();
+/// Whether a message is meant as a command, i.e. starts with the command prefix.
+pub fn is_command(value: &str) -> bool {
+    value.trim_start().starts_with(PREFIX)
+}
 
-                Some(Commands::PurgeUser(username, reason))
-            }
-            SITE_BAN_REMOVE => {
-                if parts.len() < 3 {
-                    return None;
-                }
+/// Parses durations such as `30m`, `12h`, `7d` or `2w`, a bare number is in hours.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount = amount.parse::<i64>().ok()?;
+    let unit = match unit {
+        "m" => 60,
+        "" | "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        "w" => 60 * 60 * 24 * 7,
+        _ => return None,
+    };
 
-                let username = parts[1].to_string();
-                let reason = parts[2].to_string();
+    // Reject amounts beyond the range of a duration, rather than panicking
+    amount
+        .checked_mul(unit)
+        .filter(|seconds| *seconds <= Duration::max_value().num_seconds())
+        .map(Duration::seconds)
+}
 
-                Some(Commands::SiteBanRemove(username, reason))
-            }
-            SITE_BAN => {
-                if parts.len() < 3 {
-                    return None;
-                }
+/// Parses an expiry relative to now, e.g. `7d`, or an ISO date or time, e.g. `2030-01-31`.
+///
+/// Unlike durations a unit is required, so a bare number is never mistaken for an expiry.
+pub fn parse_expiry(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date.and_hms_opt(0, 0, 0)?.and_utc());
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+
+    match value.ends_with(|c: char| c.is_ascii_digit()) {
+        true => None,
+        false => now.checked_add_signed(parse_duration(value)?),
+    }
+}
+
+pub fn format_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn format_duration(duration: &Duration) -> String {
+    let minutes = duration.num_minutes();
+    match minutes {
+        _ if minutes % (60 * 24 * 7) == 0 && minutes > 0 => format!("{}w", minutes / (60 * 24 * 7)),
+        _ if minutes % (60 * 24) == 0 && minutes > 0 => format!("{}d", minutes / (60 * 24)),
+        _ if minutes % 60 == 0 => format!("{}h", minutes / 60),
+        _ => format!("{}m", minutes),
+    }
+}
+
+pub struct Argument {
+    name: &'static str,
+    kind: Kind,
+    optional: bool,
+}
+
+impl Argument {
+    const fn required(name: &'static str, kind: Kind) -> Self {
+        Argument {
+            name,
+            kind,
+            optional: false,
+        }
+    }
+
+    const fn optional(name: &'static str, kind: Kind) -> Self {
+        Argument {
+            name,
+            kind,
+            optional: true,
+        }
+    }
+}
+
+impl Display for Argument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.optional {
+            true => write!(f, "[{}]", self.name),
+            false => write!(f, "<{}>", self.name),
+        }
+    }
+}
+
+/// Declaration of a message command, used for parsing, help and usage errors alike.
+pub struct Spec {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub summary: &'static str,
+    pub arguments: &'static [Argument],
+    pub permission: Permission,
+    /// Destructive commands are only performed once confirmed by the sender
+    pub is_destructive: bool,
+    build: fn(&mut Arguments) -> Commands,
+}
 
-                let username = parts[1].to_string();
-                let reason = parts[2].to_string();
+impl Spec {
+    pub fn find(name: &str) -> Option<&'static Spec> {
+        let name = name.trim_start_matches(PREFIX).to_lowercase();
+        COMMANDS
+            .iter()
+            .find(|spec| spec.name == name || spec.aliases.contains(&name.as_str()))
+    }
 
-                Some(Commands::SiteBan(username, reason))
+    pub fn usage(&self) -> String {
+        let mut usage = format!("{}{}", PREFIX, self.name);
+        for argument in self.arguments {
+            usage.push_str(&format!(" {}", argument));
+        }
+        usage
+    }
+}
+
+pub enum Value {
+    User(String),
+    Community(String),
+    Instance(String),
+    Number(u64),
+    Duration(Duration),
+    Expiry(DateTime<Utc>),
+    Url(String),
+    Text(String),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::User(value)
+            | Value::Community(value)
+            | Value::Instance(value)
+            | Value::Url(value) => {
+                write!(f, "{}", value)
             }
+            Value::Number(value) => write!(f, "`{}`", value),
+            Value::Duration(value) => write!(f, "`{}`", format_duration(value)),
+            Value::Expiry(value) => write!(f, "`{}`", format_time(value)),
+            Value::Text(value) => write!(f, "`{}`", value),
+        }
+    }
+}
+
+/// Parsed argument values, taken in the order they are declared.
+pub struct Arguments {
+    values: std::vec::IntoIter<Option<Value>>,
+}
+
+impl Arguments {
+    fn next(&mut self) -> Option<Value> {
+        self.values.next().flatten()
+    }
+
+    fn text(&mut self) -> String {
+        self.optional_text().unwrap_or_default()
+    }
+
+    fn optional_text(&mut self) -> Option<String> {
+        match self.next()? {
+            Value::User(value)
+            | Value::Community(value)
+            | Value::Instance(value)
+            | Value::Url(value) => Some(value),
+            Value::Text(value) => Some(value),
+            value => Some(value.to_string()),
+        }
+    }
+
+    fn number(&mut self) -> u64 {
+        self.optional_number().unwrap_or_default()
+    }
+
+    fn optional_number(&mut self) -> Option<u64> {
+        match self.next()? {
+            Value::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn optional_expiry(&mut self) -> Option<DateTime<Utc>> {
+        match self.next()? {
+            Value::Expiry(value) => Some(value),
             _ => None,
         }
     }
+
+    fn duration(&mut self) -> Duration {
+        match self.next() {
+            Some(Value::Duration(value)) => value,
+            _ => Duration::zero(),
+        }
+    }
 }
 
-impl Display for Commands {
+pub enum ParseError {
+    Unknown(String),
+    Unterminated,
+    Missing(&'static Spec, &'static Argument),
+    Invalid(&'static Spec, &'static Argument, String),
+    Unexpected(&'static Spec, String),
+}
+
+impl Display for ParseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Commands::PurgeUser(username, reason) => {
-                write!(
-                    f,
-                    "* command = `{}`\r\n\
-                     * user = {}\r\n\
-                     * reason = `{}`",
-                    PURGE_USER, username, reason
-                )
-            }
-            Commands::SiteBanRemove(username, reason) => {
-                write!(
-                    f,
-                    "* command = `{}`\r\n\
-                     * user = {}\r\n\
-                     * reason = `{}`",
-                    SITE_BAN_REMOVE, username, reason
-                )
+            ParseError::Unknown(name) => write!(
+                f,
+                "unknown command `{}`, reply with `{}{}` for a list of commands",
+                name, PREFIX, HELP
+            ),
+            ParseError::Unterminated => write!(f, "missing closing quote"),
+            ParseError::Missing(spec, argument) => {
+                write!(f, "missing {}, usage: `{}`", argument.name, spec.usage())
             }
-            Commands::SiteBan(username, reason) => {
-                write!(
-                    f,
-                    "* command = `{}`\r\n\
-                     * user = {}\r\n\
-                     * reason = `{}`",
-                    SITE_BAN, username, reason
-                )
+            ParseError::Invalid(spec, argument, value) => write!(
+                f,
+                "invalid {} `{}`, expected {}. Usage: `{}`",
+                argument.name,
+                value,
+                argument.kind.expected(),
+                spec.usage()
+            ),
+            ParseError::Unexpected(spec, value) => write!(
+                f,
+                "unexpected argument `{}`, usage: `{}`",
+                value,
+                spec.usage()
+            ),
+        }
+    }
+}
+
+/// A parsed message command along with its arguments.
+pub struct Command {
+    pub spec: &'static Spec,
+    pub action: Commands,
+    values: Vec<(&'static str, String)>,
+}
+
+impl Command {
+    pub fn parse(value: &str) -> Result<Self, ParseError> {
+        let value = value.trim();
+        let (name, mut rest) = match next_token(value)? {
+            Some((name, rest)) if name.starts_with(PREFIX) => (name, rest),
+            _ => return Err(ParseError::Unknown(value.to_string())),
+        };
+        let spec = Spec::find(&name).ok_or(ParseError::Unknown(name.to_string()))?;
+
+        let mut values = vec![];
+        for argument in spec.arguments {
+            // Free text consumes the raw remainder of the message, keeping its formatting
+            if let Kind::Text = argument.kind {
+                let text = unquote(rest.trim());
+                rest = "";
+                match text.is_empty() {
+                    true if argument.optional => values.push(None),
+                    true => return Err(ParseError::Missing(spec, argument)),
+                    false => values.push(Some(Value::Text(text.to_string()))),
+                }
+                continue;
             }
+
+            let (token, remainder) = match next_token(rest) {
+                Ok(Some(value)) => value,
+                // Leave an unterminated quote to any free text following an omitted argument
+                Ok(None) | Err(_) if argument.optional => {
+                    values.push(None);
+                    continue;
+                }
+                Ok(None) => return Err(ParseError::Missing(spec, argument)),
+                Err(err) => return Err(err),
+            };
+            let value = match argument.kind.parse(&token) {
+                Some(value) => {
+                    rest = remainder;
+                    Some(value)
+                }
+                // Skip optional arguments which don't match, e.g. an omitted expiry,
+                // though a mistyped expiry must not silently become part of the reason
+                None if argument.optional && !argument.kind.resembles(&token) => None,
+                None => return Err(ParseError::Invalid(spec, argument, token)),
+            };
+            values.push(value);
         }
+        if let Some((token, _)) = next_token(rest)? {
+            return Err(ParseError::Unexpected(spec, token));
+        }
+
+        let display = spec
+            .arguments
+            .iter()
+            .zip(values.iter())
+            .filter_map(|(argument, value)| {
+                value
+                    .as_ref()
+                    .map(|value| (argument.name, value.to_string()))
+            })
+            .collect();
+        let action = (spec.build)(&mut Arguments {
+            values: values.into_iter(),
+        });
+
+        Ok(Command {
+            spec,
+            action,
+            values: display,
+        })
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "* command = `{}`", self.spec.name)?;
+        for (name, value) in &self.values {
+            write!(f, "\r\n* {} = {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits the next argument off a message, keeping a quoted argument together.
+fn next_token(value: &str) -> Result<Option<(String, &str)>, ParseError> {
+    let value = value.trim_start();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    if let Some(quoted) = value.strip_prefix(QUOTE) {
+        let end = quoted.find(QUOTE).ok_or(ParseError::Unterminated)?;
+        return Ok(Some((quoted[..end].to_string(), &quoted[end + 1..])));
+    }
+    let end = value.find(char::is_whitespace).unwrap_or(value.len());
+    Ok(Some((value[..end].to_string(), &value[end..])))
+}
+
+/// Strips the quotes around free text given as a single quoted argument.
+fn unquote(value: &str) -> &str {
+    match value
+        .strip_prefix(QUOTE)
+        .and_then(|value| value.strip_suffix(QUOTE))
+    {
+        Some(inner) if !inner.contains(QUOTE) => inner,
+        _ => value,
     }
 }
@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Deserialize)]
 #[serde(default)]
@@ -6,9 +7,30 @@ pub struct PrivateMessage {
     pub enabled: bool,
     pub interval: i64,
     pub prune_messages: bool,
+    pub prune_interval: i64,
+    // Lemmy only lets the sender delete a message, so there is no received message age
+    pub prune_sent_age: i64,
+    pub prune_archive: bool,
     pub forward_messages: bool,
+    pub modmail: bool,
+    pub auto_reply: bool,
+    pub auto_reply_interval: i64,
+    pub auto_reply_keywords: HashMap<String, String>,
+    pub filter_messages: bool,
+    pub filter_action: FilterAction,
+    pub filter_block_sender: bool,
+    pub filter_keywords: Vec<String>,
+    pub filter_patterns: Vec<String>,
+    pub filter_links: bool,
+    pub filter_domains: Vec<String>,
+    pub filter_instances: Vec<String>,
+    pub filter_min_account_age: i64,
+    pub filter_digest_interval: i64,
     pub allow_message_commands: bool,
     pub audit_message_commands: bool,
+    pub confirmation_timeout: i64,
+    pub allow_moderator_commands: bool,
+    pub allowed_commands: HashMap<String, Vec<String>>,
 }
 
 impl Default for PrivateMessage {
@@ -17,9 +39,37 @@ impl Default for PrivateMessage {
             enabled: false,
             interval: 60,
             prune_messages: false,
+            prune_interval: 3600,
+            prune_sent_age: 604800,
+            prune_archive: false,
             forward_messages: false,
+            modmail: false,
+            auto_reply: false,
+            auto_reply_interval: 86400,
+            auto_reply_keywords: HashMap::new(),
+            filter_messages: false,
+            filter_action: FilterAction::Quarantine,
+            filter_block_sender: false,
+            filter_keywords: vec![],
+            filter_patterns: vec![],
+            filter_links: false,
+            filter_domains: vec![],
+            filter_instances: vec![],
+            filter_min_account_age: 0,
+            filter_digest_interval: 86400,
             allow_message_commands: false,
             audit_message_commands: false,
+            confirmation_timeout: 300,
+            allow_moderator_commands: false,
+            allowed_commands: HashMap::new(),
         }
     }
 }
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    Drop,
+    Quarantine,
+    Report,
+}
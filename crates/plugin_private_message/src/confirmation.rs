@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use lemmy_client::model::PrivateMessageId;
+use serde::{Deserialize, Serialize};
+
+pub const STATE: &str = "command_confirmations";
+
+/// A destructive command awaiting confirmation by its sender.
+#[derive(Serialize, Deserialize)]
+pub struct Confirmation {
+    pub code: u32,
+    pub sender_id: i32,
+    pub message_id: PrivateMessageId,
+    pub content: String,
+    pub deadline: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Confirmations {
+    pub pending: Vec<Confirmation>,
+}
+
+impl Confirmations {
+    /// Records a command awaiting confirmation, returning its one-time code.
+    pub fn request(
+        &mut self,
+        sender_id: i32,
+        message_id: PrivateMessageId,
+        content: String,
+        now: DateTime<Utc>,
+        deadline: DateTime<Utc>,
+    ) -> u32 {
+        self.prune(now);
+
+        // Derive a four digit code, skipping any still pending
+        let mut code = 1000 + now.timestamp_subsec_micros() % 9000;
+        while self.pending.iter().any(|pending| pending.code == code) {
+            code = 1000 + (code - 999) % 9000;
+        }
+
+        self.pending.push(Confirmation {
+            code,
+            sender_id,
+            message_id,
+            content,
+            deadline,
+        });
+        code
+    }
+
+    /// Takes the pending command for the code, only the original sender may confirm it.
+    pub fn confirm(&mut self, code: u32, sender_id: i32, now: DateTime<Utc>) -> Option<String> {
+        self.prune(now);
+
+        let index = self
+            .pending
+            .iter()
+            .position(|pending| pending.code == code && pending.sender_id == sender_id)?;
+        Some(self.pending.remove(index).content)
+    }
+
+    /// Messages of confirmations which haven't expired.
+    pub fn pending_messages(
+        &self,
+        now: DateTime<Utc>,
+    ) -> impl Iterator<Item = PrivateMessageId> + '_ {
+        self.pending
+            .iter()
+            .filter(move |pending| pending.deadline > now)
+            .map(|pending| pending.message_id)
+    }
+
+    /// Discards expired confirmations.
+    pub fn prune(&mut self, now: DateTime<Utc>) {
+        self.pending.retain(|pending| pending.deadline > now);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::config;
+use chrono::{DateTime, Duration, Utc};
+use lemmy_client::model::{Person, PrivateMessage};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use tracing::error;
+use url::Url;
+
+pub const STATE: &str = "message_quarantine";
+
+/// Inbound message filter, applied before replying to or forwarding a message.
+pub struct Filter {
+    keywords: Vec<String>,
+    patterns: Vec<Regex>,
+    links: bool,
+    domains: Vec<String>,
+    instances: Vec<String>,
+    min_account_age: Duration,
+    link_pattern: Regex,
+}
+
+impl Filter {
+    pub fn new(config: &config::PrivateMessage) -> Self {
+        // Skip invalid patterns, rather than failing to start
+        let patterns = config
+            .filter_patterns
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(err) => {
+                    error!("invalid filter pattern: {} -> {}", pattern, err);
+                    None
+                }
+            })
+            .collect();
+
+        Filter {
+            keywords: config
+                .filter_keywords
+                .iter()
+                .map(|keyword| keyword.to_lowercase())
+                .collect(),
+            patterns,
+            links: config.filter_links,
+            domains: config
+                .filter_domains
+                .iter()
+                .map(|domain| domain.to_lowercase())
+                .collect(),
+            instances: config
+                .filter_instances
+                .iter()
+                .map(|instance| instance.to_lowercase())
+                .collect(),
+            min_account_age: Duration::seconds(config.filter_min_account_age),
+            link_pattern: Regex::new(r"https?://[^\s)\]>]+").unwrap(),
+        }
+    }
+
+    /// Returns the reason a message should be filtered, if any.
+    pub fn check(
+        &self,
+        sender: &Person,
+        message: &PrivateMessage,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        if self.instances.contains(&sender.instance.to_lowercase()) {
+            return Some(format!("sender instance `{}`", sender.instance));
+        }
+        if now - sender.created < self.min_account_age {
+            return Some(format!(
+                "sender account created {}",
+                sender.created.to_rfc3339()
+            ));
+        }
+
+        let content = message.content.to_lowercase();
+        if let Some(keyword) = self
+            .keywords
+            .iter()
+            .find(|keyword| content.contains(*keyword))
+        {
+            return Some(format!("keyword `{}`", keyword));
+        }
+        if let Some(pattern) = self
+            .patterns
+            .iter()
+            .find(|pattern| pattern.is_match(&message.content))
+        {
+            return Some(format!("pattern `{}`", pattern));
+        }
+
+        for link in self.link_pattern.find_iter(&message.content) {
+            if self.links {
+                return Some("contains a link".to_string());
+            }
+
+            // Match domains along with any of their subdomains
+            let host = Url::parse(link.as_str())
+                .ok()
+                .and_then(|url| url.host_str().map(|host| host.to_lowercase()));
+            if let Some(host) = host {
+                let domain = self
+                    .domains
+                    .iter()
+                    .find(|domain| host == **domain || host.ends_with(&format!(".{}", domain)));
+                if let Some(domain) = domain {
+                    return Some(format!("link to `{}`", domain));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// A filtered message, held for the next digest.
+#[derive(Serialize, Deserialize)]
+pub struct Quarantined {
+    pub user: String,
+    pub content: String,
+    pub reason: String,
+    pub when: DateTime<Utc>,
+}
+
+impl Display for Quarantined {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "* user = {}\r\n* reason = {}\r\n* message = `{}`",
+            self.user, self.reason, self.content
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Quarantine {
+    pub messages: Vec<Quarantined>,
+    pub last_digest: Option<DateTime<Utc>>,
+}
+
+impl Quarantine {
+    /// Takes the quarantined messages once the digest interval has elapsed.
+    pub fn digest(&mut self, now: DateTime<Utc>, interval: Duration) -> Vec<Quarantined> {
+        let last_digest = *self.last_digest.get_or_insert(now);
+        if now - last_digest < interval || self.messages.is_empty() {
+            return vec![];
+        }
+
+        self.last_digest = Some(now);
+        std::mem::take(&mut self.messages)
+    }
+}
@@ -1,32 +1,68 @@
-use crate::commands::Commands;
+use crate::archive::Archive;
+use crate::commands::{format_time, is_command, Command, Commands, ParseError, Spec, COMMANDS};
+use crate::config::FilterAction;
+use crate::confirmation::Confirmations;
+use crate::filter::{Filter, Quarantine, Quarantined};
+use crate::modmail::Tickets;
+use crate::outcome::{Failure, Outcome};
+use crate::permission::Role;
+use crate::responder::Responder;
 use chrono::{DateTime, Duration, Utc};
-use lemmy_client::model::Person;
-use lemmy_client::person::{person_ban, person_get, person_purge, PersonRef};
+use lemmy_client::comment::comment_remove;
+use lemmy_client::community::{community_ban, community_get};
+use lemmy_client::model::{Community, Object, Person, PrivateMessageId};
+use lemmy_client::person::{
+    person_ban, person_block, person_get, person_moderates_get, person_purge, PersonRef,
+};
+use lemmy_client::post::{post_feature, post_lock, post_remove};
 use lemmy_client::private_message::{
     private_message_create, private_message_delete, private_message_list, private_message_read,
+    private_message_report,
 };
-use lemmy_client::site::site_admins_get;
+use lemmy_client::site::{resolve_object, site_admins_get};
 use lemmy_client::{model, Client, ClientError};
-use plugin_common::notify_admins;
+use plugin_common::notifier::{Event, Notification, Notifier, Severity, Snooze};
+use plugin_common::proposal;
+use plugin_common::proposal::{Proposals, Status};
+use plugin_common::state::State;
+use plugin_common::template::{
+    Message, AUTO_REPLY, AUTO_REPLY_ANSWER, COMMAND_AUDIT, COMMAND_CONFIRM, COMMAND_DENIED,
+    COMMAND_FAILED, COMMAND_HELP, COMMAND_INVALID, COMMAND_REFUSED, COMMAND_RESULT, COMMAND_USAGE,
+    MESSAGE_FORWARD, MESSAGE_QUARANTINE, MODMAIL_MESSAGE, MODMAIL_REPLY,
+};
+use std::collections::HashSet;
 use tracing::{debug, error};
 
+mod archive;
 mod commands;
 pub mod config;
+mod confirmation;
+mod filter;
+mod modmail;
+mod outcome;
+mod permission;
+mod responder;
 
 pub struct PrivateMessage {
     config: config::PrivateMessage,
+    state: State,
+    filter: Filter,
     last_run: DateTime<Utc>,
+    last_prune: Option<DateTime<Utc>>,
 }
 
 impl PrivateMessage {
-    pub fn new(config: config::PrivateMessage) -> Self {
+    pub fn new(config: config::PrivateMessage, state: State) -> Self {
         PrivateMessage {
+            filter: Filter::new(&config),
             config,
+            state,
             last_run: Utc::now(),
+            last_prune: None,
         }
     }
 
-    pub async fn run(&mut self, client: &Client) {
+    pub async fn run(&mut self, client: &Client, notifier: &mut Notifier) {
         // Ensure plugin is enabled
         if !self.config.enabled {
             return;
@@ -44,22 +80,84 @@ impl PrivateMessage {
 
         debug!("Checking private messages...");
 
-        if self.config.prune_messages {
+        let is_prune_due = self.last_prune.map_or(true, |last_prune| {
+            (now - last_prune).num_seconds() >= self.config.prune_interval
+        });
+        if self.config.prune_messages && is_prune_due {
             // Prune private messages
-            prune_messages(client, now).await;
+            self.last_prune = Some(now);
+            self.prune_messages(client, now).await;
         }
 
-        if self.config.allow_message_commands || self.config.forward_messages {
+        if self.config.allow_message_commands
+            || self.config.forward_messages
+            || self.config.auto_reply
+        {
             // Check private messages
-            self.check_messages(client).await;
+            self.check_messages(client, notifier).await;
+        }
+
+        if self.config.filter_messages && self.config.filter_action == FilterAction::Quarantine {
+            // Send a digest of quarantined messages
+            self.digest_quarantine(notifier, now);
         }
 
         debug!("Finished checking private messages!");
     }
 
-    async fn check_messages(&self, client: &Client) {
+    async fn prune_messages(&self, client: &Client, now: DateTime<Utc>) {
+        // Get all messages sent or received by the bot, before any deletion shifts the pages
+        let mut messages = vec![];
+        for page in 1.. {
+            match private_message_list(client, false, page).await {
+                Ok(value) if value.is_empty() => break,
+                Ok(value) => messages.extend(value),
+                Err(err) => {
+                    error!("{}", err);
+                    return;
+                }
+            }
+        }
+
+        // Keep messages still needed by open tickets or pending confirmations
+        let tickets = self.state.load::<Tickets>(modmail::STATE);
+        let confirmations = self.state.load::<Confirmations>(confirmation::STATE);
+        let keep = tickets
+            .open_messages()
+            .chain(confirmations.pending_messages(now))
+            .collect::<HashSet<PrivateMessageId>>();
+
+        // Find any messages older than the threshold, only the sender may delete a message
+        let age = self.config.prune_sent_age;
+        let stale_messages = messages
+            .into_iter()
+            .filter(|message| message.sender_id == client.user_id())
+            .filter(|message| age > 0 && message.created <= now - Duration::seconds(age))
+            .filter(|message| !keep.contains(&message.id))
+            .collect::<Vec<model::PrivateMessage>>();
+        if stale_messages.is_empty() {
+            return;
+        }
+
+        if self.config.prune_archive {
+            // Archive messages before deleting them
+            let mut archive = self.state.load::<Archive>(archive::STATE);
+            for message in &stale_messages {
+                archive.add(message, now);
+            }
+            self.state.save(archive::STATE, &archive);
+        }
+
+        for message in stale_messages {
+            if let Err(err) = private_message_delete(client, message.id).await {
+                error!("{}", err);
+            }
+        }
+    }
+
+    async fn check_messages(&self, client: &Client, notifier: &mut Notifier) {
         // Get any unread message
-        let unread_messages = match private_message_list(client, true).await {
+        let unread_messages = match private_message_list(client, true, 1).await {
             Ok(value) => value,
             Err(err) => {
                 error!("{}", err);
@@ -98,181 +196,679 @@ impl PrivateMessage {
                 }
             };
 
-            let is_local = person.is_local;
-            let is_admin = admins.iter().any(|admin| admin.id == person.id);
-
-            // Verify sender is a local admin
-            // NOTE: This could be extended to support limited commands for moderators
-            if is_local && is_admin {
-                if self.config.allow_message_commands {
-                    // Perform command contained within the message
-                    perform_message_commands(
-                        client,
-                        &admins,
-                        &person,
-                        &message,
-                        self.config.audit_message_commands,
-                    )
+            // Resolve the sender's permissions, only local users may perform commands
+            let role = match person.is_local && self.config.allow_message_commands {
+                true => match self.role(client, &admins, &person).await {
+                    Ok(role) => role,
+                    Err(err) => {
+                        error!("{}", err);
+                        continue;
+                    }
+                },
+                false => Role::default(),
+            };
+
+            // Handle any message of an admin as a command, others must use the command prefix
+            if !role.is_empty() && (role.is_admin() || is_command(&message.content)) {
+                // Perform command contained within the message
+                self.perform_message_commands(client, notifier, &admins, &person, &role, &message)
                     .await;
+                continue;
+            }
+
+            // Never filter, reply to or forward messages of admins, even without commands
+            if admins.iter().any(|admin| admin.id == person.id) {
+                continue;
+            }
+
+            // Filter spam and abuse before replying to or forwarding the message
+            if self.config.filter_messages {
+                if let Some(reason) = self.filter.check(&person, &message, Utc::now()) {
+                    self.filter_message(client, &person, &message, reason).await;
+                    continue;
                 }
-            } else if self.config.forward_messages {
-                // Forward message to admins
-                let body = format!(
-                    "* user = {}\r\n\
-                    * message = `{}`",
-                    person, message
-                );
-                notify_admins(client, &admins, body).await;
+            }
+
+            if self.config.auto_reply {
+                // Automatically reply to users, at most once per interval
+                self.auto_reply(client, notifier, &person, &message).await;
+            }
+
+            if self.config.forward_messages {
+                // Forward message to admins, as part of a ticket when modmail is enabled
+                let body = match self.config.modmail {
+                    true => {
+                        let mut tickets = self.state.load::<Tickets>(modmail::STATE);
+                        let ticket = tickets.open(person.id, person.to_string());
+                        ticket.record(
+                            person.name.clone(),
+                            false,
+                            message.id,
+                            message.content.clone(),
+                        );
+                        let id = ticket.id;
+                        self.state.save(modmail::STATE, &tickets);
+
+                        Message::new(MODMAIL_MESSAGE)
+                            .with("id", id)
+                            .with("user", &person)
+                            .with("message", &message)
+                    }
+                    false => Message::new(MESSAGE_FORWARD)
+                        .with("user", &person)
+                        .with("message", &message),
+                };
+                let notification = Notification::new(Event::MessageForward, body)
+                    .user(&person)
+                    .instance(person.instance.clone());
+                notifier.notify(notification);
             }
         }
     }
-}
 
-async fn prune_messages(client: &Client, now: DateTime<Utc>) {
-    // Get any read messages
-    let read_messages = match private_message_list(client, false).await {
-        Ok(value) => value,
-        Err(err) => {
-            error!("{}", err);
+    async fn filter_message(
+        &self,
+        client: &Client,
+        sender: &Person,
+        message: &model::PrivateMessage,
+        reason: String,
+    ) {
+        debug!("Filtered message from {}: {}", sender, reason);
+        match self.config.filter_action {
+            FilterAction::Drop => {}
+            FilterAction::Quarantine => {
+                let mut quarantine = self.state.load::<Quarantine>(filter::STATE);
+                quarantine.messages.push(Quarantined {
+                    user: sender.to_string(),
+                    content: message.content.clone(),
+                    reason,
+                    when: Utc::now(),
+                });
+                self.state.save(filter::STATE, &quarantine);
+            }
+            FilterAction::Report => {
+                if let Err(err) = private_message_report(client, message.id, reason).await {
+                    error!("{}", err);
+                }
+            }
+        }
+
+        if self.config.filter_block_sender {
+            if let Err(err) = person_block(client, sender.id, true).await {
+                error!("{}", err);
+            }
+        }
+    }
+
+    fn digest_quarantine(&self, notifier: &mut Notifier, now: DateTime<Utc>) {
+        let interval = Duration::seconds(self.config.filter_digest_interval);
+        let mut quarantine = self.state.load::<Quarantine>(filter::STATE);
+        let messages = quarantine.digest(now, interval);
+        self.state.save(filter::STATE, &quarantine);
+        if messages.is_empty() {
             return;
         }
-    };
 
-    // Delete any read messages older than threshold
-    let stale_threshold = now - Duration::days(7);
-    for message in read_messages {
-        // Skip other's messages
-        if message.sender_id != client.user_id() {
-            continue;
+        let body = Message::new(MESSAGE_QUARANTINE)
+            .with("count", messages.len())
+            .with(
+                "messages",
+                messages
+                    .iter()
+                    .map(|message| message.to_string())
+                    .collect::<Vec<String>>()
+                    .join("\r\n\r\n"),
+            );
+        notifier.notify(Notification::new(Event::MessageQuarantine, body));
+    }
+
+    async fn auto_reply(
+        &self,
+        client: &Client,
+        notifier: &Notifier,
+        sender: &Person,
+        message: &model::PrivateMessage,
+    ) {
+        let interval = Duration::seconds(self.config.auto_reply_interval);
+        let mut responder = self.state.load::<Responder>(responder::STATE);
+        if !responder.try_reply(sender.id, Utc::now(), interval) {
+            return;
         }
+        self.state.save(responder::STATE, &responder);
 
-        if message.created <= stale_threshold {
-            if let Err(err) = private_message_delete(client, message.id).await {
-                error!("{}", err);
-                continue;
+        // Answer any keywords, otherwise acknowledge the message
+        let answers = responder::answers(&self.config.auto_reply_keywords, &message.content);
+        let body = match answers.is_empty() {
+            true => Message::new(AUTO_REPLY).with("user", sender),
+            false => {
+                let answer = answers
+                    .iter()
+                    .map(|answer| answer.as_str())
+                    .collect::<Vec<&str>>()
+                    .join("\r\n\r\n");
+                Message::new(AUTO_REPLY_ANSWER)
+                    .with("user", sender)
+                    .with("answer", answer)
             }
+        };
+        let body = notifier.render(&body);
+        if let Err(err) = private_message_create(client, sender.id, body).await {
+            error!("{}", err);
         }
     }
-}
 
-async fn perform_message_commands(
-    client: &Client,
-    admins: &Vec<Person>,
-    sender: &Person,
-    message: &model::PrivateMessage,
-    auditlog: bool,
-) {
-    // Verify message is a command
-    let content = message.content.trim();
-    let command = match Commands::parse(content) {
-        None => {
-            let body = format!("invalid or unsupported command: `{}`", content);
+    async fn role(
+        &self,
+        client: &Client,
+        admins: &[Person],
+        sender: &Person,
+    ) -> Result<Role, ClientError> {
+        let is_admin = admins.iter().any(|admin| admin.id == sender.id);
+        let allowed = self
+            .config
+            .allowed_commands
+            .get(&sender.name)
+            .cloned()
+            .unwrap_or_default();
+
+        // Look up moderated communities, unnecessary for admins
+        let communities = match !is_admin && self.config.allow_moderator_commands {
+            true => person_moderates_get(client, sender.id)
+                .await?
+                .iter()
+                .filter(|community| community.is_local)
+                .map(|community| community.id)
+                .collect(),
+            false => vec![],
+        };
+
+        Ok(Role::new(is_admin, communities, allowed))
+    }
+
+    async fn perform_message_commands(
+        &self,
+        client: &Client,
+        notifier: &mut Notifier,
+        admins: &[Person],
+        sender: &Person,
+        role: &Role,
+        message: &model::PrivateMessage,
+    ) {
+        // Verify message is a command
+        let content = message.content.trim();
+        let command = match Command::parse(content) {
+            Ok(command) => {
+                debug!("Received command: {}", command);
+                command
+            }
+            Err(err) => {
+                let body = notifier.render(
+                    &Message::new(COMMAND_INVALID)
+                        .with("content", content)
+                        .with("error", err),
+                );
+                if let Err(err) = private_message_create(client, message.sender_id, body).await {
+                    error!("{}", err);
+                }
+                return;
+            }
+        };
+
+        // Swap a confirmation for the destructive command it confirms
+        let (command, is_confirmed) = match command.action {
+            Commands::Confirm(code) => {
+                let mut confirmations = self.state.load::<Confirmations>(confirmation::STATE);
+                let pending = u32::try_from(code)
+                    .ok()
+                    .and_then(|code| confirmations.confirm(code, sender.id, Utc::now()));
+                self.state.save(confirmation::STATE, &confirmations);
+
+                match pending.map(|content| Command::parse(&content)) {
+                    Some(Ok(command)) => (command, true),
+                    _ => {
+                        let error = format!("unknown or expired confirmation code `{}`", code);
+                        let body =
+                            notifier.render(&Message::new(COMMAND_FAILED).with("error", error));
+                        if let Err(err) =
+                            private_message_create(client, message.sender_id, body).await
+                        {
+                            error!("{}", err);
+                        }
+                        return;
+                    }
+                }
+            }
+            _ => (command, false),
+        };
+
+        // Verify sender is permitted, moderators only within their own communities
+        let is_permitted = match command_community(client, &command.action).await {
+            Ok(Some(community)) => role.is_permitted_in(command.spec, community.id),
+            Ok(None) => role.is_permitted(command.spec),
+            Err(err) => {
+                error!("{}", err);
+                let body = notifier.render(&Message::new(COMMAND_FAILED).with("error", err));
+                if let Err(err) = private_message_create(client, message.sender_id, body).await {
+                    error!("{}", err);
+                }
+                return;
+            }
+        };
+        if !is_permitted {
+            let body = Message::new(COMMAND_DENIED)
+                .with("sender", &sender.name)
+                .with("command", &command);
+            notifier.notify(Notification::new(Event::CommandDenied, body).user(sender));
+
+            let error = format!("not permitted to perform `{}`", command.spec.name);
+            let body = notifier.render(&Message::new(COMMAND_FAILED).with("error", error));
             if let Err(err) = private_message_create(client, message.sender_id, body).await {
                 error!("{}", err);
             }
             return;
         }
-        Some(command) => {
-            debug!("Received command: {}", command);
-            command
-        }
-    };
 
-    // Execute command
-    let action = command.to_string();
-    match perform_command(client, command, admins).await {
-        Ok(_) => {
-            if auditlog {
-                let body = format!(
-                    "`{}` performed a message command:\r\n{}",
-                    sender.name, action
-                );
-                notify_admins(client, admins, body.clone()).await;
+        // Reply with the permitted commands, help is not audited
+        if let Commands::Help(name) = &command.action {
+            let body = match name {
+                None => {
+                    let commands = COMMANDS
+                        .iter()
+                        .filter(|spec| role.is_permitted(spec))
+                        .map(|spec| format!("* `{}` - {}", spec.usage(), spec.summary))
+                        .collect::<Vec<String>>()
+                        .join("\r\n");
+                    Message::new(COMMAND_HELP).with("commands", commands)
+                }
+                Some(name) => match Spec::find(name).filter(|spec| role.is_permitted(spec)) {
+                    Some(spec) => {
+                        let aliases = match spec.aliases.is_empty() {
+                            true => "none".to_string(),
+                            false => spec
+                                .aliases
+                                .iter()
+                                .map(|alias| format!("`{}`", alias))
+                                .collect::<Vec<String>>()
+                                .join(", "),
+                        };
+                        Message::new(COMMAND_USAGE)
+                            .with("usage", spec.usage())
+                            .with("summary", spec.summary)
+                            .with("aliases", aliases)
+                    }
+                    None => Message::new(COMMAND_INVALID)
+                        .with("content", content)
+                        .with("error", ParseError::Unknown(name.clone())),
+                },
+            };
+            let body = notifier.render(&body);
+            if let Err(err) = private_message_create(client, message.sender_id, body).await {
+                error!("{}", err);
             }
+            return;
         }
-        // Let the sender know the command failed
-        Err(err) => {
-            let body = format!("command failed: `{}`", err);
+
+        // Destructive commands are performed once confirmed by the sender
+        if command.spec.is_destructive && !is_confirmed {
+            let now = Utc::now();
+            let deadline = now + Duration::seconds(self.config.confirmation_timeout);
+            let mut confirmations = self.state.load::<Confirmations>(confirmation::STATE);
+            let code =
+                confirmations.request(sender.id, message.id, content.to_string(), now, deadline);
+            self.state.save(confirmation::STATE, &confirmations);
+
+            let body = Message::new(COMMAND_CONFIRM)
+                .with("command", &command)
+                .with("code", code)
+                .with("deadline", format_time(&deadline));
+            let body = notifier.render(&body);
             if let Err(err) = private_message_create(client, message.sender_id, body).await {
                 error!("{}", err);
             }
+            return;
         }
-    };
+
+        // Execute command
+        let action = command.to_string();
+        let severity = match command.action {
+            Commands::PurgeUser(..) => Severity::High,
+            _ => Severity::Low,
+        };
+        let result = perform_command(
+            client,
+            notifier,
+            &self.state,
+            command.action,
+            admins,
+            sender,
+        )
+        .await;
+
+        // Always let the sender know the outcome
+        let body = match result {
+            Ok(outcome) => {
+                if self.config.audit_message_commands {
+                    let body = Message::new(COMMAND_AUDIT)
+                        .with("sender", &sender.name)
+                        .with("command", &action)
+                        .with("result", &outcome);
+                    notifier
+                        .notify(Notification::new(Event::CommandAudit, body).severity(severity));
+                }
+
+                Message::new(COMMAND_RESULT)
+                    .with("command", &action)
+                    .with("result", outcome)
+            }
+            Err(Failure::Refused(reason)) => Message::new(COMMAND_REFUSED)
+                .with("command", &action)
+                .with("reason", reason),
+            Err(Failure::Error(err)) => {
+                error!("{}", err);
+                Message::new(COMMAND_FAILED).with("error", err)
+            }
+        };
+        let body = notifier.render(&body);
+        if let Err(err) = private_message_create(client, message.sender_id, body).await {
+            error!("{}", err);
+        }
+    }
+}
+
+/// Resolves the community a command is scoped to, if any.
+async fn command_community(
+    client: &Client,
+    command: &Commands,
+) -> Result<Option<Community>, String> {
+    match command {
+        Commands::CommunityBan(name, ..) => community_get(client, name.clone())
+            .await
+            .map(Some)
+            .map_err(|err| err.to_string()),
+        Commands::Feature(url, _)
+        | Commands::Lock(url, _)
+        | Commands::Remove(url, _)
+        | Commands::Restore(url, _) => match resolve_object(client, url.clone()).await {
+            Ok(Object::Comment(_, community)) | Ok(Object::Post(_, community)) => {
+                Ok(Some(community))
+            }
+            Ok(_) => Err("link must be a post or comment".to_string()),
+            Err(err) => Err(err.to_string()),
+        },
+        _ => Ok(None),
+    }
 }
 
 async fn perform_command(
     client: &Client,
+    notifier: &mut Notifier,
+    state: &State,
     command: Commands,
     admins: &[Person],
-) -> Result<(), ClientError> {
+    sender: &Person,
+) -> Result<Outcome, Failure> {
     match command {
-        Commands::PurgeUser(username, reason) => {
-            // Get target user
-            let person = match person_get(client, PersonRef::Username(username)).await {
-                Ok(value) => value,
-                Err(err) => {
-                    error!("{}", err);
-                    return Err(err);
-                }
-            };
+        Commands::Approve(id) => perform_decision(state, id, Status::Approved, sender),
+        Commands::Reject(id) => perform_decision(state, id, Status::Rejected, sender),
+        Commands::Assign(id, admin) => {
+            let mut tickets = state.load::<Tickets>(modmail::STATE);
+            let ticket = tickets.get(id)?;
+            let assignee = admin.unwrap_or(sender.name.clone());
+            ticket.assignee = Some(assignee.clone());
+            let outcome = Outcome::new("assigned", &ticket.user)
+                .id(id)
+                .detail("assignee", format!("`{}`", assignee));
+            state.save(modmail::STATE, &tickets);
 
-            // Verify user is not a local admin
-            if admins.iter().any(|admin| admin.id == person.id) || person.id == client.user_id() {
-                return Ok(());
+            Ok(outcome)
+        }
+        Commands::Close(id) => {
+            let mut tickets = state.load::<Tickets>(modmail::STATE);
+            let ticket = tickets.get(id)?;
+            ticket.status = modmail::Status::Closed;
+            ticket.updated = Utc::now();
+            let outcome = Outcome::new("closed", &ticket.user).id(id);
+            state.save(modmail::STATE, &tickets);
+
+            Ok(outcome)
+        }
+        Commands::Reply(id, content) => {
+            let mut tickets = state.load::<Tickets>(modmail::STATE);
+            let ticket = tickets.get(id)?;
+
+            // Reply on behalf of the team, reopening the ticket if necessary
+            let body = notifier.render(&Message::new(MODMAIL_REPLY).with("message", &content));
+            let reply = private_message_create(client, ticket.user_id, body).await?;
+            ticket.status = modmail::Status::Open;
+            ticket.record(sender.name.clone(), true, reply.id, content);
+            let outcome = Outcome::new("replied", &ticket.user).id(id);
+            state.save(modmail::STATE, &tickets);
+
+            Ok(outcome)
+        }
+        Commands::Tickets(None) => {
+            let tickets = state.load::<Tickets>(modmail::STATE);
+            let pending = tickets.pending();
+            let mut outcome = Outcome::new("listed", format!("{} open ticket(s)", pending.len()));
+            for ticket in pending {
+                outcome = outcome.detail(format!("ticket `{}`", ticket.id), ticket);
             }
 
-            // Perform user purge
-            if let Err(err) = person_purge(client, person.id, Some(reason)).await {
-                error!("{}", err);
-                return Err(err);
+            Ok(outcome)
+        }
+        Commands::Tickets(Some(id)) => {
+            let mut tickets = state.load::<Tickets>(modmail::STATE);
+            let ticket = tickets.get(id)?;
+            let mut outcome = Outcome::new("shown", &*ticket).id(id);
+            for entry in &ticket.history {
+                outcome = outcome.detail("message", entry);
             }
 
-            Ok(())
+            Ok(outcome)
+        }
+        Commands::CommunityBan(name, username, reason) => {
+            // Get target community and user
+            let community = community_get(client, name).await?;
+            let person = person_get(client, PersonRef::Username(username)).await?;
+            verify_target(client, admins, &person)?;
+
+            // Perform community ban
+            community_ban(
+                client,
+                community.id,
+                person.id,
+                true,
+                None,
+                Some(reason),
+                None,
+            )
+            .await?;
+
+            let outcome = Outcome::new("banned from community", &person)
+                .id(person.id)
+                .detail("community", &community);
+            Ok(outcome)
         }
-        Commands::SiteBanRemove(username, reason) => {
-            perform_site_ban(client, admins, username, reason, true).await
+        Commands::Feature(url, featured) => {
+            // Feature within the community, site-wide featuring is left to admins
+            let post = match resolve_object(client, url).await? {
+                Object::Post(post, _) => post,
+                _ => return Err(Failure::Error("link must be a post".to_string())),
+            };
+            post_feature(client, post.id, featured, true).await?;
+
+            let action = match featured {
+                true => "featured",
+                false => "unfeatured",
+            };
+            Ok(Outcome::new(action, &post).id(post.id))
         }
-        Commands::SiteBan(username, reason) => {
-            perform_site_ban(client, admins, username, reason, false).await
+        Commands::Lock(url, locked) => {
+            let post = match resolve_object(client, url).await? {
+                Object::Post(post, _) => post,
+                _ => return Err(Failure::Error("link must be a post".to_string())),
+            };
+            post_lock(client, post.id, locked).await?;
+
+            let action = match locked {
+                true => "locked",
+                false => "unlocked",
+            };
+            Ok(Outcome::new(action, &post).id(post.id))
+        }
+        Commands::Remove(url, reason) => perform_removal(client, url, true, Some(reason)).await,
+        Commands::Restore(url, reason) => perform_removal(client, url, false, reason).await,
+        Commands::PurgeUser(username, reason) => {
+            // Get target user
+            let person = person_get(client, PersonRef::Username(username)).await?;
+            verify_target(client, admins, &person)?;
+
+            // Perform user purge
+            person_purge(client, person.id, Some(reason)).await?;
+
+            Ok(Outcome::new("purged", &person).id(person.id))
+        }
+        Commands::SiteBanRemove(username, expires, reason) => {
+            perform_site_ban(client, admins, username, reason, true, expires).await
+        }
+        Commands::SiteBan(username, expires, reason) => {
+            perform_site_ban(client, admins, username, reason, false, expires).await
+        }
+        Commands::SnoozeInstance(instance, duration) => {
+            let until = Utc::now()
+                .checked_add_signed(duration)
+                .ok_or("duration out of range".to_string())?;
+            notifier.snooze(Snooze::Instance(instance.clone()), until);
+
+            Ok(Outcome::new("snoozed", instance).detail("until", format_time(&until)))
+        }
+        Commands::SnoozeUser(username, duration) => {
+            let until = Utc::now()
+                .checked_add_signed(duration)
+                .ok_or("duration out of range".to_string())?;
+
+            // Resolve the user, notifications are keyed by name and instance
+            let person = person_get(client, PersonRef::Username(username)).await?;
+            let user = format!("{}@{}", person.name, person.instance);
+            notifier.snooze(Snooze::User(user), until);
+
+            Ok(Outcome::new("snoozed", &person)
+                .id(person.id)
+                .detail("until", format_time(&until)))
+        }
+        Commands::Unban(username, reason) => {
+            // Get target user
+            let person = person_get(client, PersonRef::Username(username)).await?;
+
+            // Perform user unban
+            person_ban(client, person.id, false, None, Some(reason), None).await?;
+
+            Ok(Outcome::new("unbanned", &person).id(person.id))
+        }
+        // Help and confirmations are handled before any command is performed
+        Commands::Help(_) | Commands::Confirm(_) => {
+            Err(Failure::Error("nothing to perform".to_string()))
         }
     }
 }
 
+/// Refuses commands targeting local admins or the bot itself.
+fn verify_target(client: &Client, admins: &[Person], person: &Person) -> Result<(), Failure> {
+    if person.id == client.user_id() {
+        return Err(Failure::Refused(format!("{} is this bot", person)));
+    }
+    if admins.iter().any(|admin| admin.id == person.id) {
+        return Err(Failure::Refused(format!("{} is a local admin", person)));
+    }
+
+    Ok(())
+}
+
+fn perform_decision(
+    state: &State,
+    id: u64,
+    status: Status,
+    sender: &Person,
+) -> Result<Outcome, Failure> {
+    // Record the decision, it will be applied on the next run of the ModLog plugin
+    let mut proposals = state.load::<Proposals>(proposal::STATE);
+    let proposal = proposals.decide(id, status, sender.name.clone())?;
+    let outcome = Outcome::new(status, &proposal.user)
+        .id(proposal.id)
+        .detail("applied", "on the next run of the ModLog plugin");
+    state.save(proposal::STATE, &proposals);
+
+    Ok(outcome)
+}
+
+async fn perform_removal(
+    client: &Client,
+    url: String,
+    removed: bool,
+    reason: Option<String>,
+) -> Result<Outcome, Failure> {
+    // Resolve the link, federated content is fetched when unknown
+    let object = resolve_object(client, url).await?;
+
+    // Perform removal or restoration
+    let id = match &object {
+        Object::Post(post, _) => {
+            post_remove(client, post.id, removed, reason).await?;
+            post.id
+        }
+        Object::Comment(comment, _) => {
+            comment_remove(client, comment.id, removed, reason).await?;
+            comment.id
+        }
+        _ => return Err(Failure::Error("link must be a post or comment".to_string())),
+    };
+
+    let action = match removed {
+        true => "removed",
+        false => "restored",
+    };
+    Ok(Outcome::new(action, &object).id(id))
+}
+
 async fn perform_site_ban(
     client: &Client,
     admins: &[Person],
     username: String,
     reason: String,
     remove_content: bool,
-) -> Result<(), ClientError> {
-    // Get target user
-    let person = match person_get(client, PersonRef::Username(username)).await {
-        Ok(value) => value,
-        Err(err) => {
-            error!("{}", err);
-            return Err(err);
-        }
-    };
-
-    // Verify user is not a local admin
-    if admins.iter().any(|admin| admin.id == person.id) || person.id == client.user_id() {
-        return Ok(());
+    expires: Option<DateTime<Utc>>,
+) -> Result<Outcome, Failure> {
+    // Verify the ban would not expire immediately
+    if expires.is_some_and(|expires| expires <= Utc::now()) {
+        return Err(Failure::Error(
+            "ban expiry must be in the future".to_string(),
+        ));
     }
 
+    // Get target user
+    let person = person_get(client, PersonRef::Username(username)).await?;
+    verify_target(client, admins, &person)?;
+
     // Perform user ban
-    if let Err(err) = person_ban(
+    person_ban(
         client,
         person.id,
         true,
         Some(remove_content),
         Some(reason),
-        None,
+        expires,
     )
-    .await
-    {
-        error!("{}", err);
-        return Err(err);
-    }
+    .await?;
 
-    Ok(())
+    let action = match remove_content {
+        true => "banned and removed content",
+        false => "banned",
+    };
+    let expires = match expires {
+        Some(expires) => format_time(&expires),
+        None => "never".to_string(),
+    };
+    Ok(Outcome::new(action, &person)
+        .id(person.id)
+        .detail("expires", expires))
 }
@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use lemmy_client::model::PrivateMessageId;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+pub const STATE: &str = "modmail_tickets";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Open,
+    Closed,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Status::Open => "open",
+            Status::Closed => "closed",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A single message of a ticket, either from the user or a reply by an admin.
+#[derive(Serialize, Deserialize)]
+pub struct Entry {
+    pub author: String,
+    pub is_reply: bool,
+    pub message_id: PrivateMessageId,
+    pub content: String,
+    pub when: DateTime<Utc>,
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} `{}`: {}",
+            self.when.format("%Y-%m-%d %H:%M"),
+            self.author,
+            self.content
+        )
+    }
+}
+
+/// A conversation with a single user, handled by the admin team.
+#[derive(Serialize, Deserialize)]
+pub struct Ticket {
+    pub id: u64,
+    pub user_id: i32,
+    pub user: String,
+    pub status: Status,
+    pub assignee: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+    pub history: Vec<Entry>,
+}
+
+impl Ticket {
+    pub fn record(
+        &mut self,
+        author: String,
+        is_reply: bool,
+        message_id: PrivateMessageId,
+        content: String,
+    ) {
+        let when = Utc::now();
+        self.updated = when;
+        self.history.push(Entry {
+            author,
+            is_reply,
+            message_id,
+            content,
+            when,
+        });
+    }
+}
+
+impl Display for Ticket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, {}", self.user, self.status)?;
+        if let Some(assignee) = &self.assignee {
+            write!(f, ", assigned to `{}`", assignee)?;
+        }
+        write!(f, ", {} message(s)", self.history.len())
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Tickets {
+    next_id: u64,
+    pub tickets: Vec<Ticket>,
+}
+
+impl Tickets {
+    /// Returns the open ticket of a user, opening a new ticket if necessary.
+    pub fn open(&mut self, user_id: i32, user: String) -> &mut Ticket {
+        let index = match self
+            .tickets
+            .iter()
+            .position(|ticket| ticket.user_id == user_id && ticket.status == Status::Open)
+        {
+            Some(index) => index,
+            None => {
+                self.next_id += 1;
+                let now = Utc::now();
+                self.tickets.push(Ticket {
+                    id: self.next_id,
+                    user_id,
+                    user,
+                    status: Status::Open,
+                    assignee: None,
+                    created: now,
+                    updated: now,
+                    history: vec![],
+                });
+                self.tickets.len() - 1
+            }
+        };
+        &mut self.tickets[index]
+    }
+
+    pub fn get(&mut self, id: u64) -> Result<&mut Ticket, String> {
+        self.tickets
+            .iter_mut()
+            .find(|ticket| ticket.id == id)
+            .ok_or(format!("unknown ticket: {}", id))
+    }
+
+    /// Tickets awaiting a response, oldest update first.
+    pub fn pending(&self) -> Vec<&Ticket> {
+        let mut tickets = self
+            .tickets
+            .iter()
+            .filter(|ticket| ticket.status == Status::Open)
+            .collect::<Vec<&Ticket>>();
+        tickets.sort_by_key(|ticket| ticket.updated);
+        tickets
+    }
+
+    /// Messages belonging to open tickets.
+    pub fn open_messages(&self) -> impl Iterator<Item = PrivateMessageId> + '_ {
+        self.tickets
+            .iter()
+            .filter(|ticket| ticket.status == Status::Open)
+            .flat_map(|ticket| ticket.history.iter().map(|entry| entry.message_id))
+    }
+}
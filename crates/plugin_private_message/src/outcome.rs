@@ -0,0 +1,67 @@
+use lemmy_client::ClientError;
+use std::fmt::{Display, Formatter};
+
+/// Result of a performed command, sent back to the sender.
+pub struct Outcome {
+    action: String,
+    target: String,
+    ids: Vec<String>,
+    details: Vec<(String, String)>,
+}
+
+impl Outcome {
+    pub fn new(action: impl ToString, target: impl ToString) -> Self {
+        Outcome {
+            action: action.to_string(),
+            target: target.to_string(),
+            ids: vec![],
+            details: vec![],
+        }
+    }
+
+    pub fn id(mut self, id: impl ToString) -> Self {
+        self.ids.push(id.to_string());
+        self
+    }
+
+    pub fn detail(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.details.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "* action = {}\r\n* target = {}",
+            self.action, self.target
+        )?;
+        if !self.ids.is_empty() {
+            write!(f, "\r\n* ids = `{}`", self.ids.join(", "))?;
+        }
+        for (name, value) in &self.details {
+            write!(f, "\r\n* {} = {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reason a command was not performed.
+pub enum Failure {
+    /// The command was deliberately not performed, e.g. targeting an admin
+    Refused(String),
+    Error(String),
+}
+
+impl From<String> for Failure {
+    fn from(value: String) -> Self {
+        Failure::Error(value)
+    }
+}
+
+impl From<ClientError> for Failure {
+    fn from(value: ClientError) -> Self {
+        Failure::Error(value.to_string())
+    }
+}
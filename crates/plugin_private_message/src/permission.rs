@@ -0,0 +1,57 @@
+use crate::commands::Spec;
+
+/// Role required to perform a command.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Permission {
+    Admin,
+    /// Moderators are limited to the communities they moderate
+    Moderator,
+}
+
+/// Permissions of a message sender, resolved once per message.
+#[derive(Default)]
+pub struct Role {
+    is_admin: bool,
+    communities: Vec<i32>,
+    allowed: Vec<String>,
+}
+
+impl Role {
+    pub fn new(is_admin: bool, communities: Vec<i32>, allowed: Vec<String>) -> Self {
+        Role {
+            is_admin,
+            communities,
+            allowed,
+        }
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.is_admin
+    }
+
+    /// Whether the sender may perform any command at all.
+    pub fn is_empty(&self) -> bool {
+        !self.is_admin && self.communities.is_empty() && self.allowed.is_empty()
+    }
+
+    /// Whether the sender may perform the command in at least one community.
+    pub fn is_permitted(&self, spec: &Spec) -> bool {
+        self.is_admin
+            || self.is_allowed(spec)
+            || (spec.permission == Permission::Moderator && !self.communities.is_empty())
+    }
+
+    /// Whether the sender may perform the command within a specific community.
+    pub fn is_permitted_in(&self, spec: &Spec, community_id: i32) -> bool {
+        self.is_admin
+            || self.is_allowed(spec)
+            || (spec.permission == Permission::Moderator
+                && self.communities.contains(&community_id))
+    }
+
+    fn is_allowed(&self, spec: &Spec) -> bool {
+        self.allowed
+            .iter()
+            .any(|name| name == "*" || name == spec.name)
+    }
+}
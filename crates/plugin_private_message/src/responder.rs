@@ -0,0 +1,37 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+pub const STATE: &str = "auto_replies";
+
+/// Tracks when each user last received an automatic reply.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Responder {
+    replied: HashMap<i32, DateTime<Utc>>,
+}
+
+impl Responder {
+    /// Records a reply to the user, unless they received one within the interval.
+    pub fn try_reply(&mut self, user_id: i32, now: DateTime<Utc>, interval: Duration) -> bool {
+        self.replied.retain(|_, when| *when > now - interval);
+        match self.replied.entry(user_id) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(now);
+                true
+            }
+        }
+    }
+}
+
+/// Canned answers for any keywords contained in the message, ordered by keyword.
+pub fn answers<'a>(keywords: &'a HashMap<String, String>, content: &str) -> Vec<&'a String> {
+    let content = content.to_lowercase();
+    let mut matches = keywords
+        .iter()
+        .filter(|(keyword, _)| content.contains(&keyword.to_lowercase()))
+        .collect::<Vec<(&String, &String)>>();
+    matches.sort_by_key(|(keyword, _)| *keyword);
+    matches.into_iter().map(|(_, answer)| answer).collect()
+}